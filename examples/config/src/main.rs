@@ -5,8 +5,8 @@ pub mod config {
     #[derive(Debug)]
     pub struct ServerConfig {
         host: String,
-        http_port: u8,
-        https_port: Option<u8>,
+        http_port: u16,
+        https_port: Option<u16>,
         log_level: u8,
         log_path: String,
     }
@@ -16,8 +16,8 @@ pub mod config {
         #[inline]
         export pub fn crate::config::make_config<S1, S2>(
             host: S1 = "0.0.0.0",
-            http_port: u8 = 80,
-            https_port: Option<u8> = Some(443),
+            http_port: u16 = 80,
+            https_port: Option<u16> = Some(443),
             log_level: u8 = 2,
             log_path: S2 = "./server.log",
         ) -> ServerConfig
@@ -48,6 +48,95 @@
 //!
 //! # More Features
 //!
+//! ## Default value using previous arguments
+//!
+//! Default value can use the arguments before it, and it would be evaluated on every call.
+//! Given arguments are evaluated in the order they are written, except the receiver of a method,
+//! which is evaluated when the method is called.
+//! Closures given out of order or before the arguments used by default values are evaluated before the call,
+//! so they may need the types of their arguments.
+//! Every argument can be named, including required arguments.
+//! Unnamed arguments should come first, and named arguments can come in any order.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     fn range(start: u32, len: u32 = 1, end: u32 = start + len) -> (u32, u32) {
+//!         (start, end)
+//!     }
+//! }
+//!
+//! assert_eq!(range!(1), (1, 2));
+//! assert_eq!(range!(1, len = 3), (1, 4));
+//! assert_eq!(range!(1, end = 10), (1, 10));
+//! assert_eq!(range!(len = 2, start = 3), (3, 5));
+//! ```
+//!
+//! Like python, arguments before `/` can only be unnamed,
+//! and arguments after `*` can only be named.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     fn scale(value: u32, /, factor: u32 = 2, *, offset: u32 = 0) -> u32 {
+//!         value * factor + offset
+//!     }
+//! }
+//!
+//! assert_eq!(scale!(1), 2);
+//! assert_eq!(scale!(1, 3), 3);
+//! assert_eq!(scale!(1, factor = 3, offset = 1), 4);
+//! ```
+//!
+//! Wrong arguments like unknown, duplicated or missing argument are reported with the argument,
+//! such as ``unknown argument `optinal`, did you mean `optional`?``.
+//! *(exported macro reports them at the macro without the suggestion,
+//! since it cannot reach this crate from other crates)*
+//!
+//! Type of default value is checked where the function is declared,
+//! so wrong default value is reported even if the macro is never called without it.
+//! *(default value of generic type is only checked when it is used)*
+//!
+//! The macro is made before the function, so the function can call itself with the macro.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     fn sum(n: u32, acc: u32 = 0) -> u32 {
+//!         if n == 0 { acc } else { sum!(n - 1, acc = acc + n) }
+//!     }
+//! }
+//!
+//! assert_eq!(sum!(4), 10);
+//! ```
+//!
+//! Several functions can be given in one macro.
+//! All macros are made before the functions, so they can be used in any of the functions.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     fn is_even(n: u32, even: bool = true) -> bool {
+//!         if n == 0 { even } else { is_odd!(n - 1, odd = even) }
+//!     }
+//!
+//!     fn is_odd(n: u32, odd: bool = true) -> bool {
+//!         if n == 0 { !odd } else { is_even!(n - 1, even = odd) }
+//!     }
+//! }
+//!
+//! assert!(is_even!(4));
+//! assert!(is_odd!(3));
+//! ```
+//!
 //! ## Export
 //!
 //! Add export in the front of the function and the macro would be exported.
@@ -71,12 +160,306 @@
 //! macro_rules! foo { () => {}; }
 //! ```
 //!
+//! ## Attribute
+//!
+//! `#[with_defaults]` is the attribute form of `default_args!`.
+//! Since `= expr` is not allowed in normal signature, default value is given by `#[default(expr)]`.
+//! *(the attribute cannot be named `default_args` since it would collide with the macro)*
+//!
+//! ```
+//! # extern crate default_args;
+//! use default_args::with_defaults;
+//!
+//! #[with_defaults(export)]
+//! pub fn foo(important_arg: u32, #[default(100)] optional: u32) -> String {
+//!     format!("{}, {}", important_arg, optional)
+//! }
+//!
+//! assert_eq!(foo!(1), "1, 100");
+//! assert_eq!(foo!(1, optional = 10), "1, 10");
+//! ```
+//!
+//! ## Name of function
+//!
+//! Function is renamed to `foo_` by default. Options can be given by `#[default_args(..)]`
+//! to keep the name (`keep_name`), to use another name (`name = bar`),
+//! or to attach another suffix (`suffix = "_impl"`).
+//! *(function and macro don't collide since they are in different namespaces)*
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[default_args(keep_name)]
+//!     pub fn foo(a: u32 = 1) -> u32 {
+//!         a
+//!     }
+//! }
+//!
+//! assert_eq!(foo!(), 1);
+//! assert_eq!(foo(2), 2);
+//! ```
+//!
+//! `#[no_mangle]` of renamed function is changed to `#[export_name = "foo"]`,
+//! so the exported symbol keeps the original name.
+//!
+//! ## Methods
+//!
+//! Wrap `impl` block with a method that has `self` and the macro would be named `Type_method`.
+//! The receiver is given as the first argument of the macro.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! struct Counter {
+//!     count: u32,
+//! }
+//!
+//! default_args! {
+//!     impl Counter {
+//!         fn add(&mut self, n: u32 = 1) {
+//!             self.count += n;
+//!         }
+//!     }
+//! }
+//!
+//! let mut counter = Counter { count: 0 };
+//! Counter_add!(counter); // counter.add_(1)
+//! Counter_add!(counter, n = 10); // counter.add_(10)
+//! assert_eq!(counter.count, 11);
+//! ```
+//!
+//! Above macro will expand as below
+//!
+//! ```
+//! # struct Counter {
+//! #     count: u32,
+//! # }
+//! impl Counter {
+//!     fn add_(&mut self, n: u32) {
+//!         self.count += n;
+//!     }
+//! }
+//!
+//! // and `Counter_add!(counter)` would be `counter.add_(1)`
+//! ```
+//!
+//! Associated functions without `self` are called through the type like `Counter::new_(..)`,
+//! and several functions can be in one `impl` block.
+//! Generic parameters of the block are inferred, and the type path starting with `crate`
+//! is called with `$crate` so it works with `export`.
+//! Name of the macro can be changed by `#[default_args(macro_name = ..)]`.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! struct Pair<T>(T, T);
+//!
+//! default_args! {
+//!     impl<T: Clone> Pair<T> {
+//!         fn new(first: T, second: T = first.clone()) -> Self {
+//!             Pair(first, second)
+//!         }
+//!
+//!         #[default_args(macro_name = swap_pair)]
+//!         fn swap(self, times: u32 = 1) -> Self {
+//!             if times % 2 == 0 { self } else { Pair(self.1, self.0) }
+//!         }
+//!     }
+//! }
+//!
+//! let pair = Pair_new!(1, 2); // Pair::new_(1, 2)
+//! assert_eq!(swap_pair!(pair).0, 2);
+//! assert_eq!(Pair_new!("a").1, "a");
+//! ```
+//!
+//! ## Traits
+//!
+//! Functions in `trait` can have default arguments too, and the macro would be named after the function.
+//! The macro calls the function of trait like `Render::render_(..)`,
+//! so the receiver is given as it is in the function, like `&widget` for `&self`.
+//! Path of trait can be written like the path of function.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     trait Render {
+//!         fn render(&self, scale: f32 = 1.0) -> String;
+//!     }
+//! }
+//!
+//! struct Widget;
+//!
+//! impl Render for Widget {
+//!     fn render_(&self, scale: f32) -> String {
+//!         format!("widget x{}", scale)
+//!     }
+//! }
+//!
+//! let widget = Widget;
+//! assert_eq!(render!(&widget), "widget x1"); // Render::render_(&widget, 1.0)
+//! assert_eq!(render!(&widget, scale = 2.0), "widget x2");
+//! ```
+//!
+//! ## Structs
+//!
+//! Fields of struct can have default values, and the macro named after the struct would construct it.
+//! Fields are given like the arguments of function, and fields of tuple struct can only be unnamed.
+//! `Default` is implemented if every field has default value.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[derive(Debug)]
+//!     pub struct ServerConfig {
+//!         pub host: &'static str = "0.0.0.0",
+//!         pub http_port: u16 = 80,
+//!         pub https_port: Option<u16> = Some(443),
+//!     }
+//! }
+//!
+//! let config = ServerConfig!("127.0.0.1", https_port = None);
+//! assert_eq!(config.http_port, 80);
+//! assert_eq!(ServerConfig::default().host, "0.0.0.0");
+//! ```
+//!
+//! ## Enums
+//!
+//! Fields of enum variants can have default values too, and the macro would be named `Enum_Variant`.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Command {
+//!         Connect { host: &'static str, port: u16 = 443 },
+//!         Ping(u32 = 0),
+//!         Quit,
+//!     }
+//! }
+//!
+//! assert_eq!(Command_Connect!("localhost"), Command::Connect { host: "localhost", port: 443 });
+//! assert_eq!(Command_Ping!(), Command::Ping(0));
+//! ```
+//!
+//! ## Builder
+//!
+//! Since macro cannot be stored or passed, `#[default_args(builder)]` makes the arguments struct
+//! named like `ConnectArgs` with setters for optional arguments, and `connect_with` taking it.
+//! Not given arguments are filled with same default values of the macro.
+//! Default generics become the defaults of the struct, so they are used like `<ParseArgs>::new(..)`.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[default_args(builder)]
+//!     fn connect(host: &str, port: u16 = 80) -> String {
+//!         format!("{}:{}", host, port)
+//!     }
+//! }
+//!
+//! let mut args = ConnectArgs::new("localhost"); // required arguments
+//! args = args.port(8080);
+//! assert_eq!(connect_with(args), "localhost:8080");
+//! assert_eq!(connect_with(ConnectArgs::new("localhost")), "localhost:80");
+//! ```
+//!
+//! ## Optional value at runtime
+//!
+//! Optional argument can be given with `Option` like `b ?= value`, and default value is used for `None`.
+//! `#[default_args(opt)]` also makes `foo_opt` taking `Option` for every optional argument.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[default_args(opt)]
+//!     fn connect(host: &str, port: u16 = 80) -> String {
+//!         format!("{}:{}", host, port)
+//!     }
+//! }
+//!
+//! let port: Option<u16> = None; // read from config file
+//! assert_eq!(connect!("localhost", port ?= port), "localhost:80");
+//! assert_eq!(connect!("localhost", port ?= Some(8080)), "localhost:8080");
+//! assert_eq!(connect_opt("localhost", None), "localhost:80");
+//! ```
+//!
+//! ## Default generics
+//!
+//! Generic parameter of function can have default like `<T = u32, const N: usize = 4>`.
+//! The defaults are given to the function when the macro is called without generics.
+//! Generics can be given in front of the arguments like `foo!(<String>; ...)`, and the rest would be the defaults.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     fn parse<T = u32>(input: &str, fallback: Option<T> = None) -> Option<T>
+//!     where
+//!         T: std::str::FromStr,
+//!     {
+//!         input.parse().ok().or(fallback)
+//!     }
+//! }
+//!
+//! assert_eq!(parse!("10"), Some(10));
+//! assert_eq!(parse!(<i8>; "-1"), Some(-1));
+//! assert_eq!(parse!(<f32>; "x", Some(0.5)), Some(0.5));
+//! ```
+//!
+//! ## Default values as items
+//!
+//! `#[default_args(defaults)]` makes the items for the default values, which are used by the macro when the path is given.
+//! Literals and paths become constants like `FOO_DEFAULT_PORT`, and others become functions like `foo_default_host()`.
+//! Default value using the previous arguments or generics is not hoisted.
+//!
+//! ```
+//! # extern crate default_args;
+//! # use default_args::default_args;
+//! #
+//! default_args! {
+//!     #[default_args(defaults)]
+//!     pub fn connect(host: String = String::from("localhost"), port: u16 = 80) -> String {
+//!         format!("{}:{}", host, port)
+//!     }
+//! }
+//!
+//! assert_eq!(CONNECT_DEFAULT_PORT, 80);
+//! assert_eq!(connect_default_host(), "localhost");
+//! assert_eq!(connect!(), "localhost:80");
+//! ```
+//!
 //! ## Path of function
 //!
 //! Macro just call the function in name, so you should import both macro and the function to use it.
 //! By writing the path of this function, you can just only import the macro.
 //! *(path should start with `crate`)*
 //!
+//! Relative paths like `self::bar` or `super::foo::bar` are not allowed,
+//! since the macro is called from other modules and they would be resolved there.
+//! The path is checked to point to the function, so the wrong path would make an error on the path.
+//!
+//! With the path, default values are evaluated where the function is declared, by hidden items like `__bar_default_a`,
+//! so they can use the items of the module that the caller doesn't import.
+//! Default value using the previous arguments or generics is still evaluated where the macro is called.
+//! Structs, enums and traits with the path work the same, and so do methods in `impl` block of the type with the path,
+//! unless the block has generic parameters.
+//! Struct or variant made with hoisted default that is not a constant can't be used in const context.
+//!
 //! ```ignore
 //! # extern crate default_args;
 //! #
@@ -111,25 +494,64 @@
 //! > `std::module_path!` can resolve the module path of the function where it is declared.
 //! > However, it can be resolved in runtime, not compile-time.
 //! > I couldn't find a way to get module path in compile-time.
+//! >
+//! > Re-exporting the function from the crate root (like `pub use` with hidden name) is not possible either,
+//! > since a macro can only make items where it is called, not in the crate root.
+//! > So exported macro without the path calls the function by its name,
+//! > and it works only where the function is imported.
+//! > It also calls itself by its name, so it should be imported instead of being called with the path like `a::foo!(..)`.
+//! > With the path, it calls itself through a hidden import next to the function, which works everywhere.
+//!
+//! ## Import with path
+//!
+//! Instead of `#[macro_use]`, the macro can be imported with path like other items
+//! by giving `use` option. It would add `use bar;` after the macro with the visibility of the function.
+//! *(`pub` becomes `pub(crate)` if the macro is not exported, since it can't be used outside the crate)*
+//!
+//! ```
+//! # extern crate default_args;
+//! pub mod foo {
+//!     # use default_args::default_args;
+//!     default_args! {
+//!         #[default_args(use)]
+//!         pub fn crate::foo::bar() {}
+//!     }
+//! }
+//!
+//! use foo::bar;
+//!
+//! # fn main() {
+//! bar!();
+//! foo::bar!();
+//! # }
+//! ```
 
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use proc_macro2::{Ident, Literal, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    parenthesized, parse_macro_input, token, Abi, Attribute, Block, Expr, FnArg, Generics, PatType,
-    ReturnType, Token, Visibility,
+    braced, bracketed, parenthesized, parse_macro_input, token, Abi, Attribute, Block, Expr, FnArg,
+    GenericParam, Generics, LitStr, Meta, Pat, PatType, Receiver, ReturnType, Token, Type,
+    Visibility,
 };
 
 /// Structure for arguments
 ///
 /// This contains arguments of function and default values like: `a: u32, b: u32 = 0`
+/// Receiver (`self`) is counted as a required argument.
+///
+/// Arguments before `/` can only be unnamed (`positional_only` is the count of them),
+/// and arguments after `*` can only be named (`keyword_only` is the index of first of them)
 struct Args {
-    parsed: Punctuated<PatType, Token![,]>,
+    parsed: Punctuated<FnArg, Token![,]>,
+    receiver: Option<Receiver>,
     required: usize,
     optional: Vec<(PatType, Expr)>,
+    positional_only: usize,
+    keyword_only: usize,
 }
 
 impl Parse for Args {
@@ -137,40 +559,93 @@ impl Parse for Args {
     ///
     /// ## Errors
     ///
-    /// - when self is the argument of the function: `self in default_args! is not support in this version`
+    /// - when self is not the first argument: `self should be the first argument`
+    /// - when self has default value: `self cannot have default value`
+    /// - when both `#[default(..)]` and `= ..` are used: `default value is given twice`
     /// - when required argument came after any optional argument: `required argument cannot come after optional argument`
+    /// - when `/` is used more than once: `` `/` is given twice ``
+    /// - when `*` is used more than once: `` `*` is given twice ``
+    /// - when `/` came after `*`: `` `/` should come before `*` ``
+    /// - when nothing came after `*`: `` `*` should be followed by arguments ``
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut args = Punctuated::new();
+        let mut receiver = None;
         let mut has_optional = false;
         let mut required = 0;
         let mut optional = Vec::new();
+        let mut positional_only = None;
+        let mut keyword_only = None;
 
         while !input.is_empty() {
-            let fn_arg = input.parse::<FnArg>()?;
+            if let Some(slash) = input.parse::<Option<Token![/]>>()? {
+                if positional_only.is_some() {
+                    return Err(syn::Error::new(slash.span(), "`/` is given twice"));
+                }
+                if keyword_only.is_some() {
+                    return Err(syn::Error::new(slash.span(), "`/` should come before `*`"));
+                }
+                positional_only = Some(args.len());
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
 
-            let pat = match fn_arg {
-                FnArg::Receiver(r) => {
+            if let Some(star) = input.parse::<Option<Token![*]>>()? {
+                if keyword_only.is_some() {
+                    return Err(syn::Error::new(star.span(), "`*` is given twice"));
+                }
+                if input.is_empty() {
                     return Err(syn::Error::new(
-                        r.span(),
-                        "self in default_args! is not support in this version",
+                        star.span(),
+                        "`*` should be followed by arguments",
                     ));
                 }
-                FnArg::Typed(pat) => pat,
-            };
+                keyword_only = Some(args.len());
+                input.parse::<Token![,]>()?;
+                continue;
+            }
 
-            if input.parse::<Option<Token![=]>>()?.is_some() {
-                has_optional = true;
-                optional.push((pat.clone(), input.parse()?));
-            } else if has_optional {
-                return Err(syn::Error::new(
-                    pat.span(),
-                    "required argument cannot come after optional argument",
-                ));
-            } else {
-                required += 1;
+            let mut fn_arg = input.parse::<FnArg>()?;
+
+            match &mut fn_arg {
+                FnArg::Receiver(r) => {
+                    if !args.is_empty() {
+                        return Err(syn::Error::new(
+                            r.span(),
+                            "self should be the first argument",
+                        ));
+                    }
+                    if input.peek(Token![=]) {
+                        return Err(syn::Error::new(r.span(), "self cannot have default value"));
+                    }
+                    receiver = Some(r.clone());
+                    required += 1;
+                }
+                FnArg::Typed(pat) => {
+                    let mut default = take_default(&mut pat.attrs)?;
+                    if let Some(eq) = input.parse::<Option<Token![=]>>()? {
+                        if default.is_some() {
+                            return Err(syn::Error::new(eq.span(), "default value is given twice"));
+                        }
+                        default = Some(input.parse()?);
+                    }
+
+                    if let Some(default) = default {
+                        has_optional = true;
+                        optional.push((pat.clone(), default));
+                    } else if has_optional {
+                        return Err(syn::Error::new(
+                            pat.span(),
+                            "required argument cannot come after optional argument",
+                        ));
+                    } else {
+                        required += 1;
+                    }
+                }
             }
 
-            args.push_value(pat);
+            args.push_value(fn_arg);
 
             if input.is_empty() {
                 break;
@@ -179,14 +654,45 @@ impl Parse for Args {
             args.push_punct(input.parse()?);
         }
 
+        let count = args.len();
         Ok(Args {
             parsed: args,
+            receiver,
             required,
             optional,
+            positional_only: positional_only.unwrap_or(0),
+            keyword_only: keyword_only.unwrap_or(count),
         })
     }
 }
 
+/// Take default value from `#[default(..)]` attribute of argument
+///
+/// The attribute is removed from `attrs` since it is not a valid attribute for function argument
+fn take_default(attrs: &mut Vec<Attribute>) -> syn::Result<Option<Expr>> {
+    let mut default = None;
+    let mut error = None;
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("default") {
+            return true;
+        }
+        if default.is_some() {
+            error = Some(syn::Error::new(attr.span(), "default value is given twice"));
+        } else {
+            match attr.parse_args() {
+                Ok(expr) => default = Some(expr),
+                Err(e) => error = Some(e),
+            }
+        }
+        false
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(default),
+    }
+}
+
 impl ToTokens for Args {
     /// This function changes to normal signature of function which is `self.parsed`
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
@@ -194,36 +700,298 @@ impl ToTokens for Args {
     }
 }
 
-/// Module for export keyword
+/// Module for custom keywords
 ///
-/// export keyword would make macro export (by adding `#[macro_export]`
-mod export {
+/// - `export` would make macro export (by adding `#[macro_export]`)
+/// - others are used in options like `#[default_args(keep_name)]`
+mod kw {
     use syn::custom_keyword;
 
+    custom_keyword!(builder);
+    custom_keyword!(defaults);
     custom_keyword!(export);
+    custom_keyword!(keep_name);
+    custom_keyword!(macro_name);
+    custom_keyword!(name);
+    custom_keyword!(opt);
+    custom_keyword!(suffix);
 }
 
-/// Structure for Default Argument function
+/// Options of the function
 ///
-/// This contains the signature of function like
-/// `#[hello] export pub const async unsafe extern "C" fn crate::foo::bar<T>(a: T, b: u32 = 0) -> String where T: Display { format!("{}, {}", a, b) }`
-struct DefaultArgs {
-    attrs: Vec<Attribute>,
-    export: Option<export::export>,
-    vis: Visibility,
-    constness: Option<Token![const]>,
-    asyncness: Option<Token![async]>,
-    unsafety: Option<Token![unsafe]>,
-    abi: Option<Abi>,
-    fn_token: Token![fn],
-    crate_path: Option<(Token![crate], Token![::])>,
-    fn_path: Punctuated<Ident, Token![::]>,
-    fn_name: Ident,
-    generics: Generics,
-    paren_token: token::Paren,
-    args: Args,
-    ret: ReturnType,
-    body: Block,
+/// This is given like `#[default_args(keep_name)]` on the function,
+/// or as the arguments of `#[with_defaults(..)]`
+///
+/// - `export`: same as `export` keyword
+/// - `keep_name`: keep the name of function instead of attaching `_`
+/// - `name = foo`: name of function
+/// - `suffix = "_impl"`: suffix attached to the name of function
+/// - `use`: import the macro with the visibility of function, so it can be used with path
+/// - `macro_name = foo`: name of macro
+/// - `builder`: make `FooArgs` struct with builder and `foo_with` function taking it
+/// - `opt`: make `foo_opt` function taking `Option` for optional arguments
+/// - `defaults`: make `FOO_DEFAULT_B` constant or `foo_default_b` function for default values
+#[derive(Clone, Default)]
+struct Options {
+    export: Option<kw::export>,
+    use_token: Option<Token![use]>,
+    keep_name: Option<kw::keep_name>,
+    name: Option<Ident>,
+    suffix: Option<LitStr>,
+    macro_name: Option<Ident>,
+    builder: Option<kw::builder>,
+    opt: Option<kw::opt>,
+    defaults: Option<kw::defaults>,
+}
+
+impl Parse for Options {
+    /// Parse function for `Options`
+    ///
+    /// ## Errors
+    ///
+    /// - when suffix makes invalid name: `suffix should make valid identifier`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = Options::default();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::export) {
+                options.export = Some(input.parse()?);
+            } else if lookahead.peek(Token![use]) {
+                options.use_token = Some(input.parse()?);
+            } else if lookahead.peek(kw::keep_name) {
+                options.keep_name = Some(input.parse()?);
+            } else if lookahead.peek(kw::name) {
+                input.parse::<kw::name>()?;
+                input.parse::<Token![=]>()?;
+                options.name = Some(input.parse()?);
+            } else if lookahead.peek(kw::builder) {
+                options.builder = Some(input.parse()?);
+            } else if lookahead.peek(kw::opt) {
+                options.opt = Some(input.parse()?);
+            } else if lookahead.peek(kw::defaults) {
+                options.defaults = Some(input.parse()?);
+            } else if lookahead.peek(kw::macro_name) {
+                input.parse::<kw::macro_name>()?;
+                input.parse::<Token![=]>()?;
+                options.macro_name = Some(input.parse()?);
+            } else if lookahead.peek(kw::suffix) {
+                input.parse::<kw::suffix>()?;
+                input.parse::<Token![=]>()?;
+                let suffix: LitStr = input.parse()?;
+                if syn::parse_str::<Ident>(&format!("a{}", suffix.value())).is_err() {
+                    return Err(syn::Error::new(
+                        suffix.span(),
+                        "suffix should make valid identifier",
+                    ));
+                }
+                options.suffix = Some(suffix);
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(options)
+    }
+}
+
+impl Options {
+    /// Merge other options into this
+    ///
+    /// ## Errors
+    ///
+    /// - when name is given in more than one way: `` only one of `keep_name`, `name` and `suffix` can be used ``
+    fn merge(&mut self, other: Options) -> syn::Result<()> {
+        self.export = self.export.or(other.export);
+        self.use_token = self.use_token.or(other.use_token);
+        self.keep_name = self.keep_name.or(other.keep_name);
+        self.name = self.name.take().or(other.name);
+        self.suffix = self.suffix.take().or(other.suffix);
+        self.macro_name = self.macro_name.take().or(other.macro_name);
+        self.builder = self.builder.or(other.builder);
+        self.opt = self.opt.or(other.opt);
+        self.defaults = self.defaults.or(other.defaults);
+
+        let names = [
+            self.keep_name.map(|k| k.span()),
+            self.name.as_ref().map(|n| n.span()),
+            self.suffix.as_ref().map(|s| s.span()),
+        ];
+        if let Some(span) = names.iter().flatten().nth(1) {
+            return Err(syn::Error::new(
+                *span,
+                "only one of `keep_name`, `name` and `suffix` can be used",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Take options from `#[default_args(..)]` attributes
+    fn take(attrs: &mut Vec<Attribute>) -> syn::Result<Self> {
+        let mut options = Options::default();
+        let mut error = None;
+        attrs.retain(|attr| {
+            if !attr.path().is_ident("default_args") {
+                return true;
+            }
+            if let Err(e) = attr.parse_args().and_then(|other| options.merge(other)) {
+                error = Some(e);
+            }
+            false
+        });
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(options),
+        }
+    }
+}
+
+/// `crate::` at the start of the path of item
+type CratePath = Option<(Token![crate], Token![::])>;
+
+/// Parse the path of item like `crate::foo::Bar`
+///
+/// Returns the `crate::` part, the path between and the name of item.
+///
+/// ## Errors
+///
+/// - when path starts with `self` or `super`: `relative path cannot be resolved where the macro is called, path should start with crate`
+/// - when path don't start with `crate`: `path should start with crate`
+fn parse_path(
+    input: ParseStream,
+) -> syn::Result<(CratePath, Punctuated<Ident, Token![::]>, Ident)> {
+    if (input.peek(Token![self]) || input.peek(Token![super])) && input.peek2(Token![::]) {
+        return Err(syn::Error::new(
+            input.span(),
+            "relative path cannot be resolved where the macro is called, path should start with crate",
+        ));
+    }
+    let crate_path = if input.peek(Token![crate]) {
+        Some((input.parse()?, input.parse()?))
+    } else {
+        None
+    };
+
+    let mut path: Punctuated<Ident, Token![::]> = Punctuated::new();
+    loop {
+        path.push_value(input.parse()?);
+        if input.peek(Token![::]) {
+            path.push_punct(input.parse()?);
+        } else {
+            break;
+        }
+    }
+
+    if crate_path.is_none() && path.len() > 1 {
+        return Err(syn::Error::new(
+            path.first().unwrap().span(),
+            "path should start with crate",
+        ));
+    }
+    let ident = path.pop().unwrap().into_value();
+    Ok((crate_path, path, ident))
+}
+
+/// Check if the next item of `impl` or `trait` block is a function
+///
+/// It looks ahead the attributes and qualifiers without consuming them
+fn peek_fn(input: ParseStream) -> syn::Result<bool> {
+    let fork = input.fork();
+    fork.call(Attribute::parse_outer)?;
+    fork.parse::<Option<kw::export>>()?;
+    fork.parse::<Visibility>()?;
+    fork.parse::<Option<Token![const]>>()?;
+    fork.parse::<Option<Token![async]>>()?;
+    fork.parse::<Option<Token![unsafe]>>()?;
+    fork.parse::<Option<Abi>>()?;
+    Ok(fork.peek(Token![fn]))
+}
+
+/// Generic parameter of function that can be given to the macro
+///
+/// Type and const parameters can have default like `<T = u32, const N: usize = 4>`,
+/// which is used in turbofish when the generics are not given to the macro
+struct GenericArg {
+    constness: bool,
+    default: Option<proc_macro2::TokenStream>,
+}
+
+impl GenericArg {
+    /// Take the defaults out of generics, since function can't have them
+    ///
+    /// Lifetimes are skipped, because they can be omitted in turbofish
+    fn take(generics: &mut Generics) -> Vec<Self> {
+        generics
+            .params
+            .iter_mut()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    Some(GenericArg {
+                        constness: false,
+                        default: param.default.take().map(|ty| ty.to_token_stream()),
+                    })
+                }
+                GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    Some(GenericArg {
+                        constness: true,
+                        default: param.default.take().map(|expr| match expr {
+                            Expr::Lit(_) | Expr::Path(_) | Expr::Block(_) => expr.to_token_stream(),
+                            _ => quote! { { #expr } },
+                        }),
+                    })
+                }
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect()
+    }
+
+    /// Turbofish with given generics and defaults of the rest
+    ///
+    /// Parameter without default is inferred with `_`, and empty turbofish is made when no default is given
+    fn turbofish(args: &[Self], given: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+        if given.is_empty() && args.iter().all(|arg| arg.default.is_none()) {
+            return quote! {};
+        }
+        let rest = args[given.len()..]
+            .iter()
+            .map(|arg| arg.default.clone().unwrap_or_else(|| quote! { _ }));
+        quote! { ::<#(#given,)* #(#rest),*> }
+    }
+}
+
+/// Structure for Default Argument function
+///
+/// This contains the signature of function like
+/// `#[hello] export pub const async unsafe extern "C" fn crate::foo::bar<T = u32>(a: T, b: u32 = 0) -> String where T: Display { format!("{}, {}", a, b) }`
+struct DefaultArgs {
+    attrs: Vec<Attribute>,
+    options: Options,
+    vis: Visibility,
+    constness: Option<Token![const]>,
+    asyncness: Option<Token![async]>,
+    unsafety: Option<Token![unsafe]>,
+    abi: Option<Abi>,
+    fn_token: Token![fn],
+    crate_path: Option<(Token![crate], Token![::])>,
+    fn_path: Punctuated<Ident, Token![::]>,
+    fn_name: Ident,
+    generics: Generics,
+    generic_args: Vec<GenericArg>,
+    paren_token: token::Paren,
+    args: Args,
+    ret: ReturnType,
+    body: Option<Block>,
+    semi_token: Option<Token![;]>,
+    impl_ty: Option<ImplType>,
+    trait_ty: Option<TraitType>,
 }
 
 impl Parse for DefaultArgs {
@@ -231,10 +999,14 @@ impl Parse for DefaultArgs {
     ///
     /// ## Errors
     ///
+    /// - when path starts with `self` or `super`: `relative path cannot be resolved where the macro is called, path should start with crate`
     /// - when path don't start with `crate`: `path should start with crate`
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let attrs = input.call(Attribute::parse_outer)?;
-        let export = input.parse()?;
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let mut options = Options::take(&mut attrs)?;
+        if let Some(export) = input.parse()? {
+            options.export = Some(export);
+        }
         let vis = input.parse()?;
         let constness = input.parse()?;
         let asyncness = input.parse()?;
@@ -242,43 +1014,24 @@ impl Parse for DefaultArgs {
         let abi = input.parse()?;
         let fn_token = input.parse()?;
 
-        let mut fn_path: Punctuated<Ident, Token![::]> = Punctuated::new();
-        let crate_token = input.parse::<Option<Token![crate]>>()?;
-        let crate_path = if let Some(token) = crate_token {
-            let crate_colon_token = input.parse::<Token![::]>()?;
-            Some((token, crate_colon_token))
-        } else {
-            None
-        };
-
-        loop {
-            fn_path.push_value(input.parse()?);
-            if input.peek(Token![::]) {
-                fn_path.push_punct(input.parse()?);
-            } else {
-                break;
-            }
-        }
-
-        if crate_path.is_none() && fn_path.len() > 1 {
-            return Err(syn::Error::new(
-                fn_path.first().unwrap().span(),
-                "path should start with crate",
-            ));
-        }
-        let fn_name = fn_path.pop().unwrap().into_value();
+        let (crate_path, fn_path, fn_name) = parse_path(input)?;
 
         let mut generics: Generics = input.parse()?;
+        let generic_args = GenericArg::take(&mut generics);
         let content;
         let paren_token = parenthesized!(content in input);
         let args = content.parse()?;
         let ret = input.parse()?;
         generics.where_clause = input.parse()?;
-        let body = input.parse()?;
+        let (body, semi_token) = if input.peek(Token![;]) {
+            (None, Some(input.parse()?))
+        } else {
+            (Some(input.parse()?), None)
+        };
 
         Ok(DefaultArgs {
             attrs,
-            export,
+            options,
             vis,
             constness,
             asyncness,
@@ -289,20 +1042,252 @@ impl Parse for DefaultArgs {
             fn_path,
             fn_name,
             generics,
+            generic_args,
             paren_token,
             args,
             ret,
             body,
+            semi_token,
+            impl_ty: None,
+            trait_ty: None,
+        })
+    }
+}
+
+impl DefaultArgs {
+    /// Name of the generated function
+    ///
+    /// It would be the name with under bar attached unless options are given
+    fn fn_ident(&self) -> Ident {
+        if self.options.keep_name.is_some() {
+            self.fn_name.clone()
+        } else if let Some(name) = &self.options.name {
+            name.clone()
+        } else if let Some(suffix) = &self.options.suffix {
+            format_ident!("{}{}", self.fn_name, suffix.value())
+        } else {
+            format_ident!("{}_", self.fn_name)
+        }
+    }
+
+    /// Check that the function has body
+    ///
+    /// ## Errors
+    ///
+    /// - when the function has no body: `function without body is only allowed in trait`
+    fn check_body(&self) -> syn::Result<()> {
+        match &self.semi_token {
+            Some(semi) => Err(syn::Error::new(
+                semi.span(),
+                "function without body is only allowed in trait",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Path of the item next to the function, used in the macro
+    fn item_path(&self, ident: impl ToTokens) -> proc_macro2::TokenStream {
+        if self.crate_path.is_some() {
+            let fn_path = &self.fn_path;
+            quote! { $crate::#fn_path#ident }
+        } else {
+            quote! { #ident }
+        }
+    }
+
+    /// Items that the default values are hoisted into
+    ///
+    /// They are public items when `defaults` option is given, and hidden ones like `__foo_default_b` when the path is given,
+    /// so the default values are evaluated where the function is declared.
+    /// Hidden ones of functions in `trait` are named with the trait like `__render_render_default_scale`,
+    /// and ones in `impl` block are associated items of the type.
+    /// Hidden one of `const fn` is only made for constant, so the macro can still be used in const context.
+    fn hoisted(&self) -> Vec<Option<Hoisted>> {
+        let hidden = self.options.defaults.is_none();
+        if hidden && self.owner().is_none() {
+            return vec![None; self.args.optional.len()];
+        }
+        let prefix = if hidden { "__" } else { "" };
+
+        let mut generics = vec![&self.generics];
+        let name = match &self.trait_ty {
+            Some(ty) => {
+                generics.push(&ty.generics);
+                format!("{}_{}", ty.name, self.fn_name)
+            }
+            None => self.fn_name.to_string(),
+        };
+        hoist(
+            &self.args,
+            &generics,
+            prefix,
+            &name,
+            hidden && self.constness.is_some(),
+        )
+    }
+
+    /// Default value of the optional argument at `index` used in the function
+    ///
+    /// Hoisted default is referred by its item next to the function
+    fn default_value(&self, index: usize, hoisted: &[Option<Hoisted>]) -> proc_macro2::TokenStream {
+        match &hoisted[index] {
+            Some(hoisted) => hoisted.item(),
+            None => self.args.optional[index].1.to_token_stream(),
+        }
+    }
+}
+
+/// Item that the default value is hoisted into
+///
+/// Literals and paths become constants like `FOO_DEFAULT_PORT`, and others become functions like `foo_default_port`
+#[derive(Clone)]
+enum Hoisted {
+    Const(Ident),
+    Fn(Ident),
+}
+
+impl Hoisted {
+    /// Expression using the item
+    fn item(&self) -> proc_macro2::TokenStream {
+        match self {
+            Hoisted::Const(ident) => quote! { #ident },
+            Hoisted::Fn(ident) => quote! { #ident() },
+        }
+    }
+}
+
+/// Decide the items that the default values are hoisted into
+///
+/// Items are named after `name`, like `{prefix}FOO_DEFAULT_B` for constant and `{prefix}foo_default_b` for function.
+/// Argument without name like field of tuple struct is named by its index.
+/// Default using previous arguments or generics (including lifetimes) is not hoisted,
+/// since it can't be evaluated without them.
+/// - `args`: arguments with default values
+/// - `generics`: generics of the item, with generics of `impl` or `trait` block if it is in it
+/// - `only_const`: if only constants can be made
+fn hoist(
+    args: &Args,
+    generics: &[&Generics],
+    prefix: &str,
+    name: &str,
+    only_const: bool,
+) -> Vec<Option<Hoisted>> {
+    let required = args.required;
+    let mut hoisted = vec![None; args.optional.len()];
+
+    let generic_idents = generics
+        .iter()
+        .flat_map(|g| &g.params)
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.to_string()),
+            GenericParam::Const(param) => Some(param.ident.to_string()),
+            GenericParam::Lifetime(_) => None,
         })
+        .chain(["impl".to_string(), "Self".to_string()])
+        .collect::<Vec<_>>();
+    let lifetimes = generics
+        .iter()
+        .flat_map(|g| g.lifetimes())
+        .map(|param| param.lifetime.ident.to_string())
+        .collect::<Vec<_>>();
+    let mut previous = generic_idents.clone();
+    for (i, fn_arg) in args.parsed.iter().enumerate() {
+        let pat = match fn_arg {
+            FnArg::Typed(pat) => pat,
+            FnArg::Receiver(_) => {
+                previous.push("self".to_string());
+                continue;
+            }
+        };
+        let arg = arg_name(pat);
+        if i >= required && (arg.is_some() || matches!(*pat.pat, Pat::Wild(_))) {
+            // field of tuple struct is named by its index
+            let arg = arg.map_or(i.to_string(), |arg| arg.to_string());
+            let ty = &pat.ty;
+            let default = &args.optional[i - required].1;
+            let constant = const_value(default);
+            if !mentions(quote! { #ty }, &generic_idents)
+                && !mentions_lifetime(quote! { #ty }, &lifetimes)
+                && !mentions(quote! { #default }, &previous)
+                && (constant || !only_const)
+            {
+                hoisted[i - required] = Some(if constant {
+                    Hoisted::Const(format_ident!(
+                        "{}{}_DEFAULT_{}",
+                        prefix,
+                        name.to_uppercase(),
+                        arg.to_uppercase()
+                    ))
+                } else {
+                    Hoisted::Fn(format_ident!(
+                        "{}{}_default_{}",
+                        prefix,
+                        name.to_lowercase(),
+                        arg
+                    ))
+                });
+            }
+        }
+        if let Some(arg) = arg {
+            previous.push(arg.to_string());
+        }
+    }
+    hoisted
+}
+
+/// Check if the expression is simple enough to be a constant
+///
+/// Only literals and paths with tuples, arrays and references of them are allowed
+fn const_value(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) | Expr::Path(_) => true,
+        Expr::Unary(unary) => matches!(unary.op, syn::UnOp::Neg(_)) && const_value(&unary.expr),
+        Expr::Paren(paren) => const_value(&paren.expr),
+        Expr::Reference(reference) => {
+            reference.mutability.is_none() && const_value(&reference.expr)
+        }
+        Expr::Tuple(tuple) => tuple.elems.iter().all(const_value),
+        Expr::Array(array) => array.elems.iter().all(const_value),
+        _ => false,
+    }
+}
+
+/// Check if the attribute is `#[no_mangle]`
+///
+/// Returns whether it is written as `#[unsafe(no_mangle)]`, or `None` if it is not `no_mangle`
+fn no_mangle(attr: &Attribute) -> Option<bool> {
+    if attr.path().is_ident("no_mangle") {
+        return Some(false);
+    }
+    if attr.path().is_ident("unsafe") {
+        if let Ok(meta) = attr.parse_args::<Meta>() {
+            if meta.path().is_ident("no_mangle") {
+                return Some(true);
+            }
+        }
     }
+    None
 }
 
 impl ToTokens for DefaultArgs {
     /// This function changes to normal signature of function
-    /// It would not print `export` and change the name with under bar attached
+    /// It would not print `export` and options, and change the name by `fn_ident`
+    /// `#[no_mangle]` of renamed function is changed to `#[export_name = ".."]` to keep the symbol
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let renamed = self.fn_ident() != self.fn_name;
         for i in &self.attrs {
-            i.to_tokens(tokens);
+            match no_mangle(i) {
+                Some(unsafety) if renamed => {
+                    let name = LitStr::new(&self.fn_name.to_string(), i.span());
+                    let pound = &i.pound_token;
+                    if unsafety {
+                        tokens.extend(quote! { #pound[unsafe(export_name = #name)] });
+                    } else {
+                        tokens.extend(quote! { #pound[export_name = #name] });
+                    }
+                }
+                _ => i.to_tokens(tokens),
+            }
         }
         self.vis.to_tokens(tokens);
         self.constness.to_tokens(tokens);
@@ -310,7 +1295,7 @@ impl ToTokens for DefaultArgs {
         self.unsafety.to_tokens(tokens);
         self.abi.to_tokens(tokens);
         self.fn_token.to_tokens(tokens);
-        format_ident!("{}_", &self.fn_name).to_tokens(tokens);
+        self.fn_ident().to_tokens(tokens);
         self.generics.lt_token.to_tokens(tokens);
         self.generics.params.to_tokens(tokens);
         self.generics.gt_token.to_tokens(tokens);
@@ -320,215 +1305,2501 @@ impl ToTokens for DefaultArgs {
         self.ret.to_tokens(tokens);
         self.generics.where_clause.to_tokens(tokens);
         self.body.to_tokens(tokens);
+        self.semi_token.to_tokens(tokens);
     }
 }
 
-/// Make unnamed arguments in macro
-/// - `count`: how many arguments
-/// - `def`: if it would be used in macro definition (will add `expr`)
-fn unnamed_args(count: usize, def: bool) -> proc_macro2::TokenStream {
-    (0..count)
-        .map(|i| {
-            let item = format_ident!("u{}", i);
-            if def {
-                if i == 0 {
-                    quote! { $#item:expr }
-                } else {
-                    quote! { , $#item:expr }
-                }
-            } else if i == 0 {
-                quote! { $#item }
-            } else {
-                quote! { , $#item }
-            }
-        })
-        .collect()
+/// Type of `impl` block that the function is in
+///
+/// `name` is used in the name of macro, `path` is used to call associated functions,
+/// and `owner` is the path of the associated items that the default values are hoisted into
+#[derive(Clone)]
+struct ImplType {
+    name: Ident,
+    path: proc_macro2::TokenStream,
+    owner: Option<proc_macro2::TokenStream>,
 }
 
-/// Make named arguments in definition of macro
-/// - `front_comma`: if it needs a front comma
-/// - `input`: default args
-/// - `macro_index`: mapped index of argument in function from macro
-fn named_args_def(
-    front_comma: bool,
-    input: &DefaultArgs,
-    macro_index: &[usize],
-) -> proc_macro2::TokenStream {
-    macro_index
-        .iter()
-        .enumerate()
-        .map(|(j, i)| {
-            let item = format_ident!("n{}", i);
-            let pat = &input.args.optional[*i].0.pat;
-            if !front_comma && j == 0 {
-                quote! { #pat = $#item:expr }
-            } else {
-                quote! { , #pat = $#item:expr }
+/// Replace the generic parameters in tokens with `_`
+///
+/// Lifetime like `'a` becomes `'_` since only the identifier after `'` is replaced
+fn infer_generics(tokens: proc_macro2::TokenStream, params: &[String]) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if params.contains(&ident.to_string()) => {
+                TokenTree::Ident(Ident::new("_", ident.span()))
             }
+            TokenTree::Group(group) => {
+                let mut new = proc_macro2::Group::new(
+                    group.delimiter(),
+                    infer_generics(group.stream(), params),
+                );
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            token => token,
         })
         .collect()
 }
 
-/// Make names arguments in macro
-/// - `front_comma`: if it needs a front comma
-/// - `input`: default args
-/// - `offset`: offset of named argument
-/// - `func_index`: whether if the function argument is provided
-fn named_args(
-    front_comma: bool,
-    input: &DefaultArgs,
-    offset: usize,
-    func_index: &[bool],
-) -> proc_macro2::TokenStream {
-    func_index
-        .iter()
-        .enumerate()
-        .map(|(i, provided)| {
-            let inner = if *provided {
-                let item = format_ident!("n{}", i + offset);
-                quote! { $#item }
-            } else {
-                let item = &input.args.optional[i + offset].1;
-                quote! { ( #item ) }
-            };
+impl ImplType {
+    /// Make the type from the type of `impl` block
+    ///
+    /// Generic parameters of the block are forwarded as `_` to be inferred,
+    /// and `crate` at the front of the path is changed to `$crate`,
+    /// so `impl<T> crate::foo::Foo<T>` would be called like `<$crate::foo::Foo<_>>::new_(..)`.
+    /// Default values are hoisted only when the path starts with `crate` and the block has no generic parameters,
+    /// since the items can't be referred without inferring them.
+    ///
+    /// ## Errors
+    ///
+    /// - when type is not a path: `type of impl block should be a path`
+    fn new(self_ty: &Type, generics: &Generics) -> syn::Result<Self> {
+        let ty = match self_ty {
+            Type::Path(ty) if ty.qself.is_none() => ty,
+            _ => {
+                return Err(syn::Error::new(
+                    self_ty.span(),
+                    "type of impl block should be a path",
+                ))
+            }
+        };
+        let name = ty.path.segments.last().unwrap().ident.clone();
 
-            if !front_comma && i == 0 {
-                quote! { #inner }
-            } else {
-                quote! { , #inner }
+        let params = generics
+            .params
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(param) => param.ident.to_string(),
+                GenericParam::Const(param) => param.ident.to_string(),
+                GenericParam::Lifetime(param) => param.lifetime.ident.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let mut path = infer_generics(ty.to_token_stream(), &params);
+        let mut owner = None;
+        if ty.path.leading_colon.is_none() && ty.path.segments[0].ident == "crate" {
+            path = quote! { $#path };
+            if params.is_empty() {
+                owner = Some(quote! { <#path>:: });
             }
-        })
-        .collect()
-}
+        }
 
-/// Generate one arm of macro
-/// - `input`: default args
-/// - `unnamed_cnt`: unnamed argument count
-/// - `offset`: offset of named argument
-/// - `macro_index`: mapped index of argument in function from macro
-/// - `func_index`: whether if the function argument is provided
-fn generate(
-    input: &DefaultArgs,
-    unnamed_cnt: usize,
-    offset: usize,
-    macro_index: &[usize],
-    func_index: &[bool],
-) -> proc_macro2::TokenStream {
-    let fn_name = format_ident!("{}_", input.fn_name);
+        Ok(ImplType { name, path, owner })
+    }
+}
 
-    let unnamed_def = unnamed_args(unnamed_cnt, true);
-    let unnamed = unnamed_args(unnamed_cnt, false);
+/// Structure for `impl` block with functions using default arguments
+///
+/// This contains the block like
+/// `impl<T> Foo<T> where T: Display { pub fn bar(&self, a: u32 = 0) -> String { format!("{}, {}", self.0, a) } }`
+struct DefaultArgsImpl {
+    attrs: Vec<Attribute>,
+    impl_token: Token![impl],
+    generics: Generics,
+    self_ty: Type,
+    brace_token: token::Brace,
+    items: Vec<ImplItem>,
+}
 
-    let named_def = named_args_def(unnamed_cnt != 0, input, macro_index);
-    let named = named_args(unnamed_cnt != 0, input, offset, func_index);
+/// Item of `impl` block
+///
+/// Functions are parsed with default arguments, and other items are kept as they are
+enum ImplItem {
+    Fn(Box<DefaultArgs>),
+    Other(Box<syn::ImplItem>),
+}
 
-    if input.crate_path.is_some() {
-        let fn_path = &input.fn_path;
-        quote! {
-            (#unnamed_def#named_def) => {
-                $crate::#fn_path#fn_name(#unnamed#named)
-            };
-        }
-    } else {
-        quote! {
-            (#unnamed_def#named_def) => {
-                #fn_name(#unnamed#named)
-            };
+impl Parse for ImplItem {
+    /// Parse function for `ImplItem`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if peek_fn(input)? {
+            Ok(ImplItem::Fn(Box::new(input.parse()?)))
+        } else {
+            Ok(ImplItem::Other(Box::new(input.parse()?)))
         }
     }
 }
 
-/// Generate macro arms recursively
-/// - `input`: default args
-/// - `unnamed_cnt`: unnamed argument count
-/// - `offset`: offset of named argument
-/// - `macro_index`: mapped index of argument in function from macro
-/// - `func_index`: whether if the function argument is provided
-/// - `stream`: token stream to append faster
-fn generate_recursive(
-    input: &DefaultArgs,
-    unnamed_cnt: usize,
-    offset: usize,
-    macro_index: &mut Vec<usize>,
-    func_index: &mut Vec<bool>,
-    stream: &mut proc_macro2::TokenStream,
-) {
-    stream.append_all(generate(
-        input,
-        unnamed_cnt,
-        offset,
-        macro_index,
-        func_index,
-    ));
+impl Parse for DefaultArgsImpl {
+    /// Parse function for `DefaultArgsImpl`
+    ///
+    /// ## Errors
+    ///
+    /// - when type is not a path: `type of impl block should be a path`
+    /// - when path is used in the method name: `path is not allowed for methods`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let impl_token = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let self_ty = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let content;
+        let brace_token = braced!(content in input);
 
-    for i in 0..func_index.len() {
-        if func_index[i] {
-            continue;
+        let impl_ty = ImplType::new(&self_ty, &generics)?;
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            let mut item: ImplItem = content.parse()?;
+            if let ImplItem::Fn(item) = &mut item {
+                item.check_body()?;
+                if item.crate_path.is_some() {
+                    return Err(syn::Error::new(
+                        item.fn_path.span(),
+                        "path is not allowed for methods",
+                    ));
+                }
+                item.impl_ty = Some(impl_ty.clone());
+            }
+            items.push(item);
         }
 
-        func_index[i] = true;
-        macro_index.push(i + offset);
-        generate_recursive(input, unnamed_cnt, offset, macro_index, func_index, stream);
-        macro_index.pop();
-        func_index[i] = false;
+        Ok(DefaultArgsImpl {
+            attrs,
+            impl_token,
+            generics,
+            self_ty,
+            brace_token,
+            items,
+        })
     }
 }
 
-/// Generates all macro arms
-/// - `input`: default args
-fn generate_macro(input: &DefaultArgs) -> proc_macro2::TokenStream {
-    let mut stream = proc_macro2::TokenStream::new();
+impl DefaultArgsImpl {
+    /// Functions in the impl block
+    fn functions(&self) -> impl Iterator<Item = &DefaultArgs> {
+        self.items.iter().filter_map(|item| match item {
+            ImplItem::Fn(item) => Some(&**item),
+            ImplItem::Other(_) => None,
+        })
+    }
+}
 
-    for i in 0..=input.args.optional.len() {
-        let mut macro_index = Vec::new();
-        let mut func_index = vec![false; input.args.optional.len() - i];
-        generate_recursive(
-            input,
-            input.args.required + i,
-            i,
-            &mut macro_index,
-            &mut func_index,
-            &mut stream,
-        );
+impl ToTokens for DefaultArgsImpl {
+    /// This function changes to normal `impl` block with the functions renamed
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for i in &self.attrs {
+            i.to_tokens(tokens);
+        }
+        self.impl_token.to_tokens(tokens);
+        self.generics.lt_token.to_tokens(tokens);
+        self.generics.params.to_tokens(tokens);
+        self.generics.gt_token.to_tokens(tokens);
+        self.self_ty.to_tokens(tokens);
+        self.generics.where_clause.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            for item in &self.items {
+                match item {
+                    ImplItem::Fn(item) => {
+                        item.to_tokens(tokens);
+                        tokens.extend(generate_defaults(&**item, &item.unsafety, item.fn_ident()));
+                    }
+                    ImplItem::Other(item) => item.to_tokens(tokens),
+                }
+            }
+        });
     }
+}
 
-    stream
+/// Trait that the function is declared in
+///
+/// `name` and `generics` are of the trait, `path` is used to call the function like `Trait::method_(..)`,
+/// `module` is the module of trait when the path is given,
+/// and `vis` is used for the import of the macro
+#[derive(Clone)]
+struct TraitType {
+    name: Ident,
+    generics: Generics,
+    path: proc_macro2::TokenStream,
+    module: Option<proc_macro2::TokenStream>,
+    vis: Visibility,
 }
 
-/// The main macro of this crate
+/// Item of `trait` block
 ///
-/// This would generate the original function and the macro
+/// Functions are parsed with default arguments, and other items are kept as they are
+enum TraitItem {
+    Fn(Box<DefaultArgs>),
+    Other(Box<syn::TraitItem>),
+}
+
+impl Parse for TraitItem {
+    /// Parse function for `TraitItem`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if peek_fn(input)? {
+            Ok(TraitItem::Fn(Box::new(input.parse()?)))
+        } else {
+            Ok(TraitItem::Other(Box::new(input.parse()?)))
+        }
+    }
+}
+
+/// Structure for `trait` with functions using default arguments
+///
+/// This contains the trait like
+/// `pub trait crate::foo::Render: Debug { fn render(&self, scale: f32 = 1.0) -> String; }`
+/// Path of the trait works like the path of function
+struct DefaultArgsTrait {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    unsafety: Option<Token![unsafe]>,
+    trait_token: Token![trait],
+    crate_path: Option<(Token![crate], Token![::])>,
+    trait_path: Punctuated<Ident, Token![::]>,
+    ident: Ident,
+    generics: Generics,
+    colon_token: Option<Token![:]>,
+    supertraits: Punctuated<syn::TypeParamBound, Token![+]>,
+    brace_token: token::Brace,
+    items: Vec<TraitItem>,
+}
+
+impl Parse for DefaultArgsTrait {
+    /// Parse function for `DefaultArgsTrait`
+    ///
+    /// ## Errors
+    ///
+    /// - when path starts with `self` or `super`: `relative path cannot be resolved where the macro is called, path should start with crate`
+    /// - when path don't start with `crate`: `path should start with crate`
+    /// - when path is used in the method name: `path is not allowed for methods`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let unsafety = input.parse()?;
+        let trait_token = input.parse()?;
+
+        let (crate_path, trait_path, ident) = parse_path(input)?;
+
+        let mut generics: Generics = input.parse()?;
+        let colon_token: Option<Token![:]> = input.parse()?;
+        let mut supertraits = Punctuated::new();
+        if colon_token.is_some() {
+            while !input.peek(Token![where]) && !input.peek(token::Brace) {
+                supertraits.push_value(input.parse()?);
+                if input.peek(Token![+]) {
+                    supertraits.push_punct(input.parse()?);
+                } else {
+                    break;
+                }
+            }
+        }
+        generics.where_clause = input.parse()?;
+        let content;
+        let brace_token = braced!(content in input);
+
+        let path = match &crate_path {
+            Some(_) => quote! { $crate::#trait_path #ident },
+            None => quote! { #ident },
+        };
+        let trait_ty = TraitType {
+            name: ident.clone(),
+            generics: generics.clone(),
+            path,
+            module: crate_path.map(|_| quote! { $crate::#trait_path }),
+            vis: vis.clone(),
+        };
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            let mut item: TraitItem = content.parse()?;
+            if let TraitItem::Fn(item) = &mut item {
+                if item.crate_path.is_some() {
+                    return Err(syn::Error::new(
+                        item.fn_path.span(),
+                        "path is not allowed for methods",
+                    ));
+                }
+                item.trait_ty = Some(trait_ty.clone());
+            }
+            items.push(item);
+        }
+
+        Ok(DefaultArgsTrait {
+            attrs,
+            vis,
+            unsafety,
+            trait_token,
+            crate_path,
+            trait_path,
+            ident,
+            generics,
+            colon_token,
+            supertraits,
+            brace_token,
+            items,
+        })
+    }
+}
+
+impl DefaultArgsTrait {
+    /// Functions in the trait
+    fn functions(&self) -> impl Iterator<Item = &DefaultArgs> {
+        self.items.iter().filter_map(|item| match item {
+            TraitItem::Fn(item) => Some(&**item),
+            TraitItem::Other(_) => None,
+        })
+    }
+}
+
+impl ToTokens for DefaultArgsTrait {
+    /// This function changes to normal `trait` with the functions renamed
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for i in &self.attrs {
+            i.to_tokens(tokens);
+        }
+        self.vis.to_tokens(tokens);
+        self.unsafety.to_tokens(tokens);
+        self.trait_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.lt_token.to_tokens(tokens);
+        self.generics.params.to_tokens(tokens);
+        self.generics.gt_token.to_tokens(tokens);
+        self.colon_token.to_tokens(tokens);
+        self.supertraits.to_tokens(tokens);
+        self.generics.where_clause.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            for item in &self.items {
+                match item {
+                    TraitItem::Fn(item) => item.to_tokens(tokens),
+                    TraitItem::Other(item) => item.to_tokens(tokens),
+                }
+            }
+        });
+    }
+}
+
+/// Fields with default values
+///
+/// This contains named fields like `{ pub a: u32, b: u32 = 0 }`,
+/// or unnamed fields like `(pub u32, u32 = 0)` which can only be given as unnamed arguments.
+/// Fields are also parsed as `Args`, so they work like the arguments of function
+struct Fields {
+    delimiter: FieldsDelimiter,
+    fields: Punctuated<syn::Field, Token![,]>,
+    args: Args,
+}
+
+/// Delimiter of fields
+enum FieldsDelimiter {
+    Brace(token::Brace),
+    Paren(token::Paren),
+}
+
+impl Parse for Fields {
+    /// Parse function for `Fields`
+    ///
+    /// Default value is given by `= ..` after the type or `#[default(..)]` attribute
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let delimiter = if input.peek(token::Paren) {
+            FieldsDelimiter::Paren(parenthesized!(content in input))
+        } else {
+            FieldsDelimiter::Brace(braced!(content in input))
+        };
+        let named = matches!(delimiter, FieldsDelimiter::Brace(_));
+
+        let mut fields = Punctuated::new();
+        let mut args = proc_macro2::TokenStream::new();
+        while !content.is_empty() {
+            let mut field = if named {
+                syn::Field::parse_named(&content)?
+            } else {
+                syn::Field::parse_unnamed(&content)?
+            };
+            let (defaults, attrs) = field
+                .attrs
+                .drain(..)
+                .partition::<Vec<_>, _>(|attr| attr.path().is_ident("default"));
+            field.attrs = attrs;
+
+            let pat = match &field.ident {
+                Some(ident) => ident.to_token_stream(),
+                None => quote! { _ },
+            };
+            let ty = &field.ty;
+            args.extend(quote! { #(#defaults)* #pat: #ty });
+            if let Some(eq) = content.parse::<Option<Token![=]>>()? {
+                let default: Expr = content.parse()?;
+                args.extend(quote! { #eq #default });
+            }
+
+            fields.push_value(field);
+            if content.is_empty() {
+                break;
+            }
+            let comma: Token![,] = content.parse()?;
+            args.extend(quote! { #comma });
+            fields.push_punct(comma);
+        }
+
+        let mut args: Args = syn::parse2(args)?;
+        if !named {
+            args.positional_only = args.parsed.len();
+        }
+
+        Ok(Fields {
+            delimiter,
+            fields,
+            args,
+        })
+    }
+}
+
+impl Fields {
+    /// Make the expression constructing the value with arguments
+    /// - `path`: path of struct or enum variant
+    /// - `args`: arguments in order
+    fn construct(
+        &self,
+        path: proc_macro2::TokenStream,
+        args: &[proc_macro2::TokenStream],
+    ) -> proc_macro2::TokenStream {
+        match &self.delimiter {
+            FieldsDelimiter::Brace(_) => {
+                let fields = self.fields.iter().zip(args).map(|(field, arg)| {
+                    let name = &field.ident;
+                    if quote! { #name }.to_string() == arg.to_string() {
+                        arg.clone()
+                    } else {
+                        quote! { #name: #arg }
+                    }
+                });
+                quote! { #path { #(#fields),* } }
+            }
+            FieldsDelimiter::Paren(_) => quote! { #path(#(#args),*) },
+        }
+    }
+}
+
+impl ToTokens for Fields {
+    /// This function changes to normal fields without default values
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match &self.delimiter {
+            FieldsDelimiter::Brace(brace) => brace.surround(tokens, |tokens| {
+                self.fields.to_tokens(tokens);
+            }),
+            FieldsDelimiter::Paren(paren) => paren.surround(tokens, |tokens| {
+                self.fields.to_tokens(tokens);
+            }),
+        }
+    }
+}
+
+/// Structure for struct with default values of fields
+///
+/// This contains the struct like
+/// `#[derive(Debug)] export pub struct crate::foo::Config<T> where T: Display { pub name: T, pub port: u16 = 80 }`
+/// Path of the struct works like the path of function
+struct DefaultArgsStruct {
+    attrs: Vec<Attribute>,
+    options: Options,
+    vis: Visibility,
+    struct_token: Token![struct],
+    crate_path: Option<(Token![crate], Token![::])>,
+    struct_path: Punctuated<Ident, Token![::]>,
+    ident: Ident,
+    generics: Generics,
+    fields: Fields,
+    semi_token: Option<Token![;]>,
+}
+
+impl Parse for DefaultArgsStruct {
+    /// Parse function for `DefaultArgsStruct`
+    ///
+    /// ## Errors
+    ///
+    /// - when path starts with `self` or `super`: `relative path cannot be resolved where the macro is called, path should start with crate`
+    /// - when path don't start with `crate`: `path should start with crate`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let mut options = Options::take(&mut attrs)?;
+        if let Some(export) = input.parse()? {
+            options.export = Some(export);
+        }
+        let vis = input.parse()?;
+        let struct_token = input.parse()?;
+
+        let (crate_path, struct_path, ident) = parse_path(input)?;
+
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let fields: Fields = input.parse()?;
+        let mut semi_token = None;
+        if let FieldsDelimiter::Paren(_) = fields.delimiter {
+            generics.where_clause = input.parse()?;
+            semi_token = Some(input.parse()?);
+        }
+
+        Ok(DefaultArgsStruct {
+            attrs,
+            options,
+            vis,
+            struct_token,
+            crate_path,
+            struct_path,
+            ident,
+            generics,
+            fields,
+            semi_token,
+        })
+    }
+}
+
+impl Target for DefaultArgsStruct {
+    fn args(&self) -> &Args {
+        &self.fields.args
+    }
+
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn vis(&self) -> &Visibility {
+        &self.vis
+    }
+
+    /// Macro is named after the struct
+    fn macro_name(&self) -> Ident {
+        match &self.options.macro_name {
+            Some(name) => name.clone(),
+            None => self.ident.clone(),
+        }
+    }
+
+    fn module(&self) -> Option<proc_macro2::TokenStream> {
+        let struct_path = &self.struct_path;
+        self.crate_path.map(|_| quote! { $crate::#struct_path })
+    }
+
+    /// Default values are hoisted into hidden items like `__config_default_port` when the path is given
+    fn hoisted(&self) -> Vec<Option<Hoisted>> {
+        match self.crate_path {
+            Some(_) => hoist(
+                self.args(),
+                &[&self.generics],
+                "__",
+                &self.ident.to_string(),
+                false,
+            ),
+            None => vec![None; self.args().optional.len()],
+        }
+    }
+
+    /// Make the struct with arguments
+    fn call(&self, args: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+        let struct_path = &self.struct_path;
+        let ident = &self.ident;
+        let path = match &self.crate_path {
+            Some(_) => quote! { $crate::#struct_path #ident },
+            None => quote! { #ident },
+        };
+        self.fields.construct(path, args)
+    }
+}
+
+impl ToTokens for DefaultArgsStruct {
+    /// This function changes to normal struct
+    ///
+    /// `Default` is implemented with the macro if every field has default value
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for i in &self.attrs {
+            i.to_tokens(tokens);
+        }
+        self.vis.to_tokens(tokens);
+        self.struct_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.lt_token.to_tokens(tokens);
+        self.generics.params.to_tokens(tokens);
+        self.generics.gt_token.to_tokens(tokens);
+        if self.semi_token.is_none() {
+            self.generics.where_clause.to_tokens(tokens);
+        }
+        self.fields.to_tokens(tokens);
+        if self.semi_token.is_some() {
+            self.generics.where_clause.to_tokens(tokens);
+        }
+        self.semi_token.to_tokens(tokens);
+
+        if self.fields.args.required == 0 {
+            let ident = &self.ident;
+            let name = self.macro_name();
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+            tokens.extend(quote! {
+                impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+                    fn default() -> Self {
+                        #name!()
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Variant of enum with default values of fields
+///
+/// Variant without fields is kept as it is, and it doesn't make a macro
+struct DefaultArgsVariant {
+    attrs: Vec<Attribute>,
+    options: Options,
+    ident: Ident,
+    fields: Option<Fields>,
+    discriminant: Option<(Token![=], Expr)>,
+    enum_ident: Ident,
+    enum_path: proc_macro2::TokenStream,
+    generics: Generics,
+    module: Option<proc_macro2::TokenStream>,
+    vis: Visibility,
+}
+
+impl Target for DefaultArgsVariant {
+    fn args(&self) -> &Args {
+        &self.fields.as_ref().unwrap().args
+    }
+
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn vis(&self) -> &Visibility {
+        &self.vis
+    }
+
+    /// Variants would be `Enum_Variant` to avoid collision between enums
+    fn macro_name(&self) -> Ident {
+        match &self.options.macro_name {
+            Some(name) => name.clone(),
+            None => format_ident!("{}_{}", self.enum_ident, self.ident),
+        }
+    }
+
+    fn module(&self) -> Option<proc_macro2::TokenStream> {
+        self.module.clone()
+    }
+
+    /// Default values are hoisted into hidden items like `__shape_circle_default_radius` when the path is given
+    fn hoisted(&self) -> Vec<Option<Hoisted>> {
+        match self.module {
+            Some(_) => hoist(
+                self.args(),
+                &[&self.generics],
+                "__",
+                &format!("{}_{}", self.enum_ident, self.ident),
+                false,
+            ),
+            None => vec![None; self.args().optional.len()],
+        }
+    }
+
+    /// Make the variant with arguments
+    fn call(&self, args: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+        let enum_path = &self.enum_path;
+        let ident = &self.ident;
+        self.fields
+            .as_ref()
+            .unwrap()
+            .construct(quote! { #enum_path::#ident }, args)
+    }
+}
+
+impl ToTokens for DefaultArgsVariant {
+    /// This function changes to normal variant
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for i in &self.attrs {
+            i.to_tokens(tokens);
+        }
+        self.ident.to_tokens(tokens);
+        self.fields.to_tokens(tokens);
+        if let Some((eq, expr)) = &self.discriminant {
+            eq.to_tokens(tokens);
+            expr.to_tokens(tokens);
+        }
+    }
+}
+
+/// Structure for enum with default values of fields in variants
+///
+/// This contains the enum like
+/// `#[derive(Debug)] export pub enum crate::foo::Command { Connect { host: String, port: u16 = 443 }, Ping(u32 = 0), Quit }`
+/// Path of the enum works like the path of function, and options are applied to every variant
+struct DefaultArgsEnum {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    enum_token: Token![enum],
+    crate_path: Option<(Token![crate], Token![::])>,
+    enum_path: Punctuated<Ident, Token![::]>,
+    ident: Ident,
+    generics: Generics,
+    brace_token: token::Brace,
+    variants: Punctuated<DefaultArgsVariant, Token![,]>,
+}
+
+impl Parse for DefaultArgsEnum {
+    /// Parse function for `DefaultArgsEnum`
+    ///
+    /// ## Errors
+    ///
+    /// - when path starts with `self` or `super`: `relative path cannot be resolved where the macro is called, path should start with crate`
+    /// - when path don't start with `crate`: `path should start with crate`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let mut options = Options::take(&mut attrs)?;
+        if let Some(export) = input.parse()? {
+            options.export = Some(export);
+        }
+        let vis: Visibility = input.parse()?;
+        let enum_token = input.parse()?;
+
+        let (crate_path, enum_path, ident) = parse_path(input)?;
+
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let content;
+        let brace_token = braced!(content in input);
+
+        let path = match &crate_path {
+            Some(_) => quote! { $crate::#enum_path #ident },
+            None => quote! { #ident },
+        };
+        let module = crate_path.map(|_| quote! { $crate::#enum_path });
+        let mut variants = Punctuated::new();
+        while !content.is_empty() {
+            let mut attrs = content.call(Attribute::parse_outer)?;
+            let mut variant_options = Options::take(&mut attrs)?;
+            variant_options.merge(options.clone())?;
+            let variant_ident = content.parse()?;
+            let fields = if content.peek(token::Brace) || content.peek(token::Paren) {
+                Some(content.parse()?)
+            } else {
+                None
+            };
+            let discriminant = match content.parse::<Option<Token![=]>>()? {
+                Some(eq) => Some((eq, content.parse()?)),
+                None => None,
+            };
+
+            variants.push_value(DefaultArgsVariant {
+                attrs,
+                options: variant_options,
+                ident: variant_ident,
+                fields,
+                discriminant,
+                enum_ident: ident.clone(),
+                enum_path: path.clone(),
+                generics: generics.clone(),
+                module: module.clone(),
+                vis: vis.clone(),
+            });
+            if content.is_empty() {
+                break;
+            }
+            variants.push_punct(content.parse()?);
+        }
+
+        Ok(DefaultArgsEnum {
+            attrs,
+            vis,
+            enum_token,
+            crate_path,
+            enum_path,
+            ident,
+            generics,
+            brace_token,
+            variants,
+        })
+    }
+}
+
+impl DefaultArgsEnum {
+    /// Variants with fields
+    fn variants(&self) -> impl Iterator<Item = &DefaultArgsVariant> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.fields.is_some())
+    }
+}
+
+impl ToTokens for DefaultArgsEnum {
+    /// This function changes to normal enum
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        for i in &self.attrs {
+            i.to_tokens(tokens);
+        }
+        self.vis.to_tokens(tokens);
+        self.enum_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.lt_token.to_tokens(tokens);
+        self.generics.params.to_tokens(tokens);
+        self.generics.gt_token.to_tokens(tokens);
+        self.generics.where_clause.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            self.variants.to_tokens(tokens);
+        });
+    }
+}
+
+/// Input of `default_args!`
+///
+/// This is either a function, an `impl` block or a `trait` with functions, a struct or an enum
+enum DefaultArgsInput {
+    Fn(Box<DefaultArgs>),
+    Impl(Box<DefaultArgsImpl>),
+    Trait(Box<DefaultArgsTrait>),
+    Struct(Box<DefaultArgsStruct>),
+    Enum(Box<DefaultArgsEnum>),
+}
+
+impl Parse for DefaultArgsInput {
+    /// Parse function for `DefaultArgsInput`
+    ///
+    /// ## Errors
+    ///
+    /// - when self is used outside of impl or trait block: `self is only allowed in impl or trait block`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        fork.call(Attribute::parse_outer)?;
+        if fork.peek(Token![impl]) {
+            return Ok(DefaultArgsInput::Impl(Box::new(input.parse()?)));
+        }
+        fork.parse::<Option<kw::export>>()?;
+        fork.parse::<Visibility>()?;
+        if fork.peek(Token![struct]) {
+            return Ok(DefaultArgsInput::Struct(Box::new(input.parse()?)));
+        }
+        if fork.peek(Token![enum]) {
+            return Ok(DefaultArgsInput::Enum(Box::new(input.parse()?)));
+        }
+        fork.parse::<Option<Token![unsafe]>>()?;
+        if fork.peek(Token![trait]) {
+            return Ok(DefaultArgsInput::Trait(Box::new(input.parse()?)));
+        }
+
+        let item: DefaultArgs = input.parse()?;
+        item.check_body()?;
+        if let Some(r) = &item.args.receiver {
+            return Err(syn::Error::new(
+                r.span(),
+                "self is only allowed in impl or trait block",
+            ));
+        }
+        Ok(DefaultArgsInput::Fn(Box::new(item)))
+    }
+}
+
+/// Inputs of `default_args!`
+///
+/// Several functions and `impl` blocks can be given in one macro
+struct DefaultArgsInputs(Vec<DefaultArgsInput>);
+
+impl Parse for DefaultArgsInputs {
+    /// Parse function for `DefaultArgsInputs`
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut inputs = Vec::new();
+        loop {
+            inputs.push(input.parse()?);
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(DefaultArgsInputs(inputs))
+    }
+}
+
+/// Name of argument that can be used as named argument
+///
+/// Only simple identifier pattern like `a: u32` or `mut a: u32` can be named
+fn arg_name(pat: &PatType) -> Option<&Ident> {
+    match &*pat.pat {
+        Pat::Ident(pat) if pat.subpat.is_none() => Some(&pat.ident),
+        _ => None,
+    }
+}
+
+/// Name to bind the argument in macro
+///
+/// Argument that cannot be named is bound to `__arg{index}`
+fn binding(pat: &PatType, index: usize) -> Ident {
+    match arg_name(pat) {
+        Some(ident) => ident.clone(),
+        None => format_ident!("__arg{}", index),
+    }
+}
+
+/// Check if the tokens use any of the given identifiers
+fn mentions(tokens: proc_macro2::TokenStream, idents: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Group(group) => mentions(group.stream(), idents),
+        TokenTree::Ident(ident) => idents.iter().any(|i| ident == i),
+        _ => false,
+    })
+}
+
+/// Check if the tokens use any of the given lifetimes
+fn mentions_lifetime(tokens: proc_macro2::TokenStream, lifetimes: &[String]) -> bool {
+    let mut quote = false;
+    tokens.into_iter().any(|token| {
+        let found = match &token {
+            TokenTree::Group(group) => mentions_lifetime(group.stream(), lifetimes),
+            TokenTree::Ident(ident) => quote && lifetimes.iter().any(|l| ident == l),
+            _ => false,
+        };
+        quote = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '\'');
+        found
+    })
+}
+
+/// Make slots of arguments in macro
+///
+/// Each slot is `()` when the argument is not given yet, `($t $e)` when it is given and `(? $t $e)` when it is given with `?=`.
+/// `$t` is the temporary that the argument is evaluated into when named arguments are not in order,
+/// and `($t $e)` is also kept in the order list to evaluate them in the order they are written.
+/// Receiver is kept as `($e)` without temporary.
+/// - `count`: how many slots
+/// - `def`: if it would be used in macro definition (will add `tt`)
+fn slots(count: usize, def: bool) -> Vec<proc_macro2::TokenStream> {
+    (0..count)
+        .map(|i| {
+            let item = format_ident!("s{}", i);
+            if def {
+                quote! { $#item:tt }
+            } else {
+                quote! { $#item }
+            }
+        })
+        .collect()
+}
+
+/// Name of the temporary for the argument
+///
+/// It is made in the arm consuming the argument, and used in the arm calling the function by the slot,
+/// so both would have the same hygiene
+fn temp(index: usize) -> Ident {
+    format_ident!("__tmp{}", index)
+}
+
+/// Item that the macro is generated for
+///
+/// The macro fills the arguments and calls the function or constructs the value with them
+trait Target {
+    /// Arguments of the macro
+    fn args(&self) -> &Args;
+
+    /// Options given to the item
+    fn options(&self) -> &Options;
+
+    /// Visibility of the item, used for the import of the macro
+    fn vis(&self) -> &Visibility;
+
+    /// Name of the generated macro
+    fn macro_name(&self) -> Ident;
+
+    /// Module of the item like `$crate::a::b::`, when the path is given
+    fn module(&self) -> Option<proc_macro2::TokenStream> {
+        None
+    }
+
+    /// Generic parameters that can be given to the macro like `foo!(<String>; ...)`
+    fn generic_args(&self) -> &[GenericArg] {
+        &[]
+    }
+
+    /// Path of the items that the default values are hoisted into
+    fn owner(&self) -> Option<proc_macro2::TokenStream> {
+        self.module()
+    }
+
+    /// Items that the default values are hoisted into, when the path is given
+    fn hoisted(&self) -> Vec<Option<Hoisted>> {
+        vec![None; self.args().optional.len()]
+    }
+
+    /// Default value of the optional argument at `index`
+    ///
+    /// Hoisted default is referred by its item with the path, so it is evaluated where the item is declared.
+    /// Macro without the path keeps the expression, since the item can't be referred from other modules.
+    fn default_value(&self, index: usize) -> proc_macro2::TokenStream {
+        match (&self.hoisted()[index], self.owner()) {
+            (Some(hoisted), Some(owner)) => {
+                let item = hoisted.item();
+                quote! { #owner #item }
+            }
+            _ => self.args().optional[index].1.to_token_stream(),
+        }
+    }
+
+    /// Make the expression using arguments
+    /// - `args`: arguments in order
+    fn call(&self, args: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream;
+}
+
+impl Target for DefaultArgs {
+    fn args(&self) -> &Args {
+        &self.args
+    }
+
+    fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn vis(&self) -> &Visibility {
+        self.trait_ty.as_ref().map_or(&self.vis, |ty| &ty.vis)
+    }
+
+    /// Functions in `impl` block would be `Type_name` to avoid collision between types
+    fn macro_name(&self) -> Ident {
+        match (&self.options.macro_name, &self.impl_ty) {
+            (Some(name), _) => name.clone(),
+            (None, Some(ty)) => format_ident!("{}_{}", ty.name, self.fn_name),
+            (None, None) => self.fn_name.clone(),
+        }
+    }
+
+    /// Function in `trait` block is in the module of the trait
+    fn module(&self) -> Option<proc_macro2::TokenStream> {
+        if let Some(trait_ty) = &self.trait_ty {
+            return trait_ty.module.clone();
+        }
+        let fn_path = &self.fn_path;
+        self.crate_path.map(|_| quote! { $crate::#fn_path })
+    }
+
+    fn generic_args(&self) -> &[GenericArg] {
+        &self.generic_args
+    }
+
+    /// Default values of functions in `impl` block are hoisted into associated items of the type
+    fn owner(&self) -> Option<proc_macro2::TokenStream> {
+        match &self.impl_ty {
+            Some(ty) => ty.owner.clone(),
+            None => self.module(),
+        }
+    }
+
+    fn hoisted(&self) -> Vec<Option<Hoisted>> {
+        DefaultArgs::hoisted(self)
+    }
+
+    /// Make the function call with arguments
+    ///
+    /// Turbofish made by the macro is attached to the function name as `$($g)*`
+    fn call(&self, args: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+        let fn_name = self.fn_ident();
+        let fn_name = quote! { #fn_name $($g)* };
+
+        if let Some(trait_ty) = &self.trait_ty {
+            let path = &trait_ty.path;
+            quote! { #path::#fn_name(#(#args),*) }
+        } else if self.args.receiver.is_some() {
+            let receiver = &args[0];
+            let args = &args[1..];
+            quote! { #receiver.#fn_name(#(#args),*) }
+        } else if let Some(impl_ty) = &self.impl_ty {
+            let path = &impl_ty.path;
+            quote! { <#path>::#fn_name(#(#args),*) }
+        } else {
+            let path = self.item_path(fn_name);
+            quote! { #path(#(#args),*) }
+        }
+    }
+}
+
+/// Generate arms for named arguments
+///
+/// Named argument fills the slot of the argument, and no more unnamed argument is allowed after.
+/// `$p` becomes `_` after named argument, and `*` when the argument comes before the argument already given,
+/// so the arguments are evaluated into temporaries in the order they are written.
+/// Both required and optional arguments can be named, except arguments before `/`.
+/// Optional argument can be given like `b ?= value` with `Option`, which fills the slot with `(? $t value)`.
+/// - `input`: target of macro
+fn generate_named(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = recursion(input);
+    let count = input.args().required + input.args().optional.len();
+
+    input
+        .args()
+        .parsed
+        .iter()
+        .enumerate()
+        .skip(input.args().positional_only)
+        .filter_map(|(index, fn_arg)| match fn_arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat) => Some((index, arg_name(pat)?)),
+        })
+        .map(|(index, arg)| {
+            let mut def = slots(count, true);
+            def[index] = quote! { () };
+            let mut in_order = def.clone();
+            for slot in &mut in_order[index + 1..] {
+                *slot = quote! { () };
+            }
+            let temp = temp(index);
+
+            let arms = |op: proc_macro2::TokenStream, slot: proc_macro2::TokenStream| {
+                let mut given = slots(count, false);
+                given[index] = slot;
+                let mut given_in_order = given.clone();
+                for slot in &mut given_in_order[index + 1..] {
+                    *slot = quote! { () };
+                }
+
+                quote! {
+                    (@munch [#(#def)*] * [$($o:tt)*] $g:tt ; #arg #op $e:expr $(, $($rest:tt)*)?) => {
+                        #name!(@munch [#(#given)*] * [$($o)* (#temp $e)] $g ; $($($rest)*)?)
+                    };
+                    (@munch [#(#in_order)*] $p:tt [$($o:tt)*] $g:tt ; #arg #op $e:expr $(, $($rest:tt)*)?) => {
+                        #name!(@munch [#(#given_in_order)*] _ [$($o)* (#temp $e)] $g ; $($($rest)*)?)
+                    };
+                    (@munch [#(#def)*] $p:tt [$($o:tt)*] $g:tt ; #arg #op $e:expr $(, $($rest:tt)*)?) => {
+                        #name!(@munch [#(#given)*] * [$($o)* (#temp $e)] $g ; $($($rest)*)?)
+                    };
+                }
+            };
+
+            let given = arms(quote! { = }, quote! { (#temp $e) });
+            let optional = if index < input.args().required {
+                quote! {}
+            } else {
+                arms(quote! { ?= }, quote! { (? #temp $e) })
+            };
+
+            quote! {
+                #given
+                #optional
+            }
+        })
+        .collect()
+}
+
+/// Generate arms for unnamed arguments
+///
+/// `$p` in the arm is the index of next unnamed argument, or `_` or `*` if named argument came.
+/// Arguments after `*` cannot be unnamed.
+/// - `input`: target of macro
+fn generate_unnamed(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = recursion(input);
+    let count = input.args().required + input.args().optional.len();
+
+    (0..input.args().keyword_only)
+        .map(|index| {
+            let mut def = slots(count, true);
+            def[index] = quote! { () };
+            let mut slots = slots(count, false);
+            let order = if index == 0 && input.args().receiver.is_some() {
+                slots[index] = quote! { ($e) };
+                quote! { $($o)* }
+            } else {
+                let temp = temp(index);
+                slots[index] = quote! { (#temp $e) };
+                quote! { $($o)* (#temp $e) }
+            };
+
+            let p = Literal::usize_unsuffixed(index);
+            let next = Literal::usize_unsuffixed(index + 1);
+
+            quote! {
+                (@munch [#(#def)*] #p [$($o:tt)*] $g:tt ; $e:expr $(, $($rest:tt)*)?) => {
+                    #name!(@munch [#(#slots)*] #next [#order] $g ; $($($rest)*)?)
+                };
+            }
+        })
+        .collect()
+}
+
+/// Make the expression calling the function with the slots
+///
+/// Arguments are bound to their names in order up to the last one needed by default values, so default value can use them.
+/// Default value using the arguments is also bound before the call, since the arguments are moved into the call.
+/// Arguments after them are passed to the function directly, to keep the type inference of the function (like closures).
+/// - `input`: target of macro
+/// - `temps`: if the given arguments are already evaluated into temporaries
+fn generate_call_expr(input: &impl Target, temps: bool) -> proc_macro2::TokenStream {
+    let name = recursion(input);
+    let required = input.args().required;
+    let arg = if temps {
+        quote! { @tmp }
+    } else {
+        quote! { @arg }
+    };
+    let defaults = (0..input.args().optional.len())
+        .map(|index| input.default_value(index))
+        .collect::<Vec<_>>();
+    let names = input
+        .args()
+        .parsed
+        .iter()
+        .enumerate()
+        .map(|(i, fn_arg)| match fn_arg {
+            FnArg::Receiver(_) => "self".to_string(),
+            FnArg::Typed(pat) => binding(pat, i).to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    let last = names
+        .iter()
+        .enumerate()
+        .filter(|(i, name)| {
+            defaults
+                .iter()
+                .skip((i + 1).saturating_sub(required))
+                .any(|default| mentions(default.clone(), &[name.to_string()]))
+                || (*i >= required && mentions(defaults[i - required].clone(), &names[..*i]))
+        })
+        .map(|(i, _)| i + 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut args = Vec::new();
+    let mut bindings = Vec::new();
+    for (i, fn_arg) in input.args().parsed.iter().enumerate() {
+        let item = format_ident!("s{}", i);
+        let pat = match fn_arg {
+            FnArg::Receiver(_) => {
+                args.push(quote! { $#item });
+                continue;
+            }
+            FnArg::Typed(pat) => pat,
+        };
+
+        let value = if i < required && temps {
+            let temp = format_ident!("t{}", i);
+            quote! { $#temp }
+        } else if i < required {
+            quote! { $#item }
+        } else {
+            let default = &defaults[i - required];
+            quote! { #name!(#arg $#item (#default)) }
+        };
+
+        let binding = binding(pat, i);
+        if i < last {
+            args.push(binding.to_token_stream());
+            bindings.push((binding, value));
+        } else {
+            args.push(value);
+        }
+    }
+
+    let mut call = input.call(&args);
+    for (binding, value) in bindings.into_iter().rev() {
+        call = quote! {
+            match #value {
+                #binding => #call
+            }
+        };
+    }
+    call
+}
+
+/// Generate arms for calling the function after every argument is consumed
+///
+/// Required argument should be given, and optional argument is filled with default value if not given.
+/// When named arguments are not in order, given arguments are evaluated into temporaries first.
+/// Argument given with `?=` uses default value when it is `None`.
+/// - `input`: target of macro
+fn generate_call(input: &impl Target) -> proc_macro2::TokenStream {
+    let required = input.args().required;
+
+    let def = input
+        .args()
+        .parsed
+        .iter()
+        .enumerate()
+        .map(|(i, fn_arg)| {
+            let item = format_ident!("s{}", i);
+            let temp = format_ident!("t{}", i);
+            match fn_arg {
+                FnArg::Receiver(_) => quote! { ($#item:expr) },
+                FnArg::Typed(_) if i < required => quote! { ($#temp:ident $#item:expr) },
+                FnArg::Typed(_) => quote! { $#item:tt },
+            }
+        })
+        .collect::<Vec<_>>();
+    let call = generate_call_expr(input, false);
+    let temps = generate_call_expr(input, true);
+
+    quote! {
+        (@munch [#(#def)*] * [$(($t:ident $e:expr))*] [$($g:tt)*] ;) => {
+            match ($($e,)*) {
+                ($($t,)*) => #temps
+            }
+        };
+        (@munch [#(#def)*] $p:tt $o:tt [$($g:tt)*] ;) => {
+            #call
+        };
+        (@arg (? $t:ident $e:expr) ($($d:tt)*)) => {
+            match $e {
+                ::core::option::Option::Some(__value) => __value,
+                ::core::option::Option::None => $($d)*,
+            }
+        };
+        (@arg ($t:ident $e:expr) $d:tt) => {
+            $e
+        };
+        (@arg () ($($d:tt)*)) => {
+            $($d)*
+        };
+        (@tmp (? $t:ident $e:expr) ($($d:tt)*)) => {
+            match $t {
+                ::core::option::Option::Some(__value) => __value,
+                ::core::option::Option::None => $($d)*,
+            }
+        };
+        (@tmp ($t:ident $e:expr) $d:tt) => {
+            $t
+        };
+        (@tmp () ($($d:tt)*)) => {
+            $($d)*
+        };
+    }
+}
+
+/// Generate the check that the path of function or trait points to the item itself
+///
+/// It would be like: `const _: () = { use crate::a::b::foo_ as _; };` with the span of the path
+fn generate_path_check(
+    crate_path: &Option<(Token![crate], Token![::])>,
+    path: &Punctuated<Ident, Token![::]>,
+    ident: &Ident,
+) -> proc_macro2::TokenStream {
+    let (crate_token, crate_colon) = match crate_path {
+        Some(crate_path) => crate_path,
+        None => return quote! {},
+    };
+
+    quote! {
+        #[allow(unused_imports)]
+        const _: () = {
+            use #crate_token #crate_colon #path #ident as _;
+        };
+    }
+}
+
+/// Generate function that checks the type of default values
+///
+/// Since default value is only used in the macro, type mismatch would not be reported until
+/// the macro is called without the argument. This function is never called,
+/// but it makes the compiler check the default values in the place where the function is declared.
+/// Default value with generic type is not checked,
+/// and hoisted default value is checked by its item, so it is not reported twice.
+/// Each default value is checked with only the arguments before it, like in the macro.
+/// - `args`: arguments with default values
+/// - `generics`: generics of the item, with generics of `impl` or `trait` block if it is in it
+/// - `unsafety`: whether default values can use unsafe operations
+/// - `hoisted`: items that the default values are hoisted into
+fn generate_check(
+    args: &Args,
+    generics: &[&Generics],
+    unsafety: &Option<Token![unsafe]>,
+    hoisted: &[Option<Hoisted>],
+) -> proc_macro2::TokenStream {
+    let generics = generics.iter();
+    let params = generics.clone().flat_map(|g| &g.params).collect::<Vec<_>>();
+    let predicates = generics
+        .flat_map(|g| &g.where_clause)
+        .flat_map(|w| &w.predicates)
+        .collect::<Vec<_>>();
+    let generic_idents = params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.to_string()),
+            GenericParam::Const(param) => Some(param.ident.to_string()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .chain(Some("impl".to_string()))
+        .collect::<Vec<_>>();
+
+    let mut bindings = Vec::new();
+    let mut checks = Vec::new();
+    for (i, fn_arg) in args.parsed.iter().enumerate() {
+        let pat = match fn_arg {
+            FnArg::Receiver(_) => continue,
+            FnArg::Typed(pat) => pat,
+        };
+        let ty = &pat.ty;
+        if mentions(quote! { #ty }, &["Self".to_string()]) {
+            // `Self` can't be used outside of `impl` block
+            return quote! {};
+        }
+
+        let binding = binding(pat, i);
+        if i < args.required {
+            bindings.push(quote! { #binding: #ty });
+        } else if mentions(quote! { #ty }, &generic_idents) || hoisted[i - args.required].is_some()
+        {
+            let param = format_ident!("__check{}", i);
+            bindings.push(quote! { #param: #ty });
+            checks.push(quote! { let #binding: #ty = #param; });
+        } else {
+            let default = &args.optional[i - args.required].1;
+            checks.push(quote! { let #binding: #ty = #default; });
+        }
+    }
+
+    let body = if unsafety.is_some() {
+        quote! { unsafe { #(#checks)* } }
+    } else {
+        quote! { #(#checks)* }
+    };
+
+    quote! {
+        #[allow(unused, clippy::all)]
+        const _: () = {
+            #unsafety fn __check<#(#params),*>(#(#bindings),*)
+            where
+                #(#predicates,)*
+            {
+                #body
+            }
+        };
+    }
+}
+
+/// Generate arms for errors in arguments
+///
+/// Returns arms for named argument, missing argument and unnamed argument.
+/// Errors pointing to the argument are made by `__error!` of this crate.
+/// Exported macro can't reach `__error!` from other crates, so it reports them by `compile_error!` with the messages
+/// known where the function is declared, and reports unknown argument without the suggestion.
+/// - `input`: target of macro
+fn generate_errors(
+    input: &impl Target,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let name = input.macro_name();
+    let count = input.args().required + input.args().optional.len();
+
+    let names = input
+        .args()
+        .parsed
+        .iter()
+        .enumerate()
+        .filter_map(|(index, fn_arg)| match fn_arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat) => Some((index, arg_name(pat)?)),
+        });
+    let named = names
+        .clone()
+        .filter(|(index, _)| *index >= input.args().positional_only)
+        .map(|(_, arg)| arg)
+        .collect::<Vec<_>>();
+    let positional = names
+        .clone()
+        .filter(|(index, _)| *index < input.args().positional_only)
+        .map(|(_, arg)| arg)
+        .collect::<Vec<_>>();
+    let required = names
+        .clone()
+        .filter(|(index, _)| {
+            *index >= input.args().positional_only && *index < input.args().required
+        })
+        .map(|(_, arg)| arg)
+        .collect::<Vec<_>>();
+
+    let known = names
+        .map(|(index, arg)| {
+            let (given, optional) = if index < input.args().positional_only {
+                let message = format!("argument `{}` cannot be named", arg);
+                (message.clone(), message)
+            } else if index < input.args().required {
+                (
+                    format!("argument `{}` given twice", arg),
+                    "`?=` can only be used for optional argument".to_string(),
+                )
+            } else {
+                let message = format!("argument `{}` given twice", arg);
+                (message.clone(), message)
+            };
+
+            quote! {
+                (@munch $s:tt $p:tt $o:tt $g:tt ; #arg = $($rest:tt)*) => {
+                    compile_error!(#given)
+                };
+                (@munch $s:tt $p:tt $o:tt $g:tt ; #arg ?= $($rest:tt)*) => {
+                    compile_error!(#optional)
+                };
+            }
+        })
+        .collect::<proc_macro2::TokenStream>();
+
+    let named_error = if input.options().export.is_some() {
+        quote! {
+            #known
+            (@munch $s:tt $p:tt $o:tt $g:tt ; $n:ident = $($rest:tt)*) => {
+                compile_error!(concat!("unknown argument `", stringify!($n), "`"))
+            };
+            (@munch $s:tt $p:tt $o:tt $g:tt ; $n:ident ?= $($rest:tt)*) => {
+                compile_error!(concat!("unknown argument `", stringify!($n), "`"))
+            };
+        }
+    } else {
+        quote! {
+            (@munch $s:tt $p:tt $o:tt $g:tt ; $n:ident = $($rest:tt)*) => {
+                ::default_args::__error!($n [#(#named)*] [#(#positional)*])
+            };
+            (@munch $s:tt $p:tt $o:tt $g:tt ; $n:ident ?= $($rest:tt)*) => {
+                ::default_args::__error!($n [#(#named)*] [#(#positional)*] [#(#required)*])
+            };
+        }
+    };
+
+    let missing = (0..input.args().required)
+        .map(|index| {
+            let mut def = slots(count, true);
+            def[index] = quote! { () };
+            let message = match &input.args().parsed[index] {
+                FnArg::Receiver(_) => "missing required argument `self`".to_string(),
+                FnArg::Typed(pat) => match &*pat.pat {
+                    Pat::Wild(_) => format!("missing required argument at index {}", index),
+                    pat => format!("missing required argument `{}`", quote! { #pat }),
+                },
+            };
+
+            quote! {
+                (@munch [#(#def)*] $p:tt $o:tt $g:tt ;) => {
+                    compile_error!(#message)
+                };
+            }
+        })
+        .collect::<proc_macro2::TokenStream>();
+
+    let keyword_only = Literal::usize_unsuffixed(input.args().keyword_only);
+    let invalid = format!("invalid arguments for `{}!`", name);
+    let (after_named, too_many) = if input.options().export.is_some() {
+        (
+            quote! { compile_error!("unnamed argument cannot come after named argument") },
+            quote! { compile_error!("too many unnamed arguments") },
+        )
+    } else {
+        (
+            quote! { ::default_args::__error!($t "unnamed argument cannot come after named argument") },
+            quote! { ::default_args::__error!($t "too many unnamed arguments") },
+        )
+    };
+    let unnamed_error = quote! {
+        (@munch $s:tt _ $o:tt $g:tt ; $t:tt $($rest:tt)*) => {
+            #after_named
+        };
+        (@munch $s:tt * $o:tt $g:tt ; $t:tt $($rest:tt)*) => {
+            #after_named
+        };
+        (@munch $s:tt #keyword_only $o:tt $g:tt ; $t:tt $($rest:tt)*) => {
+            #too_many
+        };
+        (@munch $($rest:tt)*) => {
+            compile_error!(#invalid)
+        };
+    };
+
+    (named_error, missing, unnamed_error)
+}
+
+/// Generate arms for explicit generics like `foo!(<String, 4>; ...)`
+///
+/// Each arm takes the first generics, and the rest would be the default or inferred.
+/// The turbofish is kept in the state of macro until the function is called.
+/// - `input`: target of macro
+fn generate_generics(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = recursion(input);
+    let count = input.args().required + input.args().optional.len();
+    let args = input.generic_args();
+
+    (1..=args.len())
+        .map(|len| {
+            let def = args[..len].iter().enumerate().map(|(i, arg)| {
+                let item = format_ident!("g{}", i);
+                if arg.constness {
+                    quote! { $#item:tt }
+                } else {
+                    quote! { $#item:ty }
+                }
+            });
+            let given = (0..len)
+                .map(|i| {
+                    let item = format_ident!("g{}", i);
+                    quote! { $#item }
+                })
+                .collect::<Vec<_>>();
+            let turbofish = GenericArg::turbofish(args, &given);
+            let empty = (0..count).map(|_| quote! { () });
+
+            quote! {
+                (<#(#def),*> ; $($args:tt)*) => {
+                    #name!(@munch [#(#empty)*] 0 [] [#turbofish] ; $($args)*)
+                };
+            }
+        })
+        .collect()
+}
+
+/// Generates all macro arms
+///
+/// Macro consumes arguments one by one and fill the slots of arguments,
+/// so the number of arms grows linearly to the number of arguments
+/// - `input`: target of macro
+fn generate_macro(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = recursion(input);
+    let count = input.args().required + input.args().optional.len();
+
+    let named = generate_named(input);
+    let unnamed = generate_unnamed(input);
+    let call = generate_call(input);
+    let (named_error, missing, unnamed_error) = generate_errors(input);
+    let generics = generate_generics(input);
+    let turbofish = GenericArg::turbofish(input.generic_args(), &[]);
+    let empty = (0..count).map(|_| quote! { () });
+
+    quote! {
+        #named
+        #named_error
+        #call
+        #missing
+        #unnamed
+        #unnamed_error
+        #generics
+        ($($args:tt)*) => {
+            #name!(@munch [#(#empty)*] 0 [] [#turbofish] ; $($args)*)
+        };
+    }
+}
+
+/// Name of `macro_rules!` when the macro is imported with `use` option
+///
+/// The macro is defined with hidden name and imported with its name,
+/// so the import doesn't collide with the item of same name like `keep_name` function or struct
+fn hidden_name(name: &Ident) -> Ident {
+    format_ident!("__default_args_{}", name)
+}
+
+/// Path of the macro used when the macro calls itself
+///
+/// Macro with the path calls itself through the import next to the item when it is exported or imported with `use`,
+/// so it works when it is called with the path like `crate::a::foo!(..)` or `a::foo!(..)` in other crates.
+/// Exported macro can't call itself with `$crate::foo!` in the same crate, since it is made by a macro.
+fn recursion(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = input.macro_name();
+    match (input.module(), input.options().export) {
+        (Some(module), _) if input.options().use_token.is_some() => quote! { #module #name },
+        (Some(module), Some(_)) => {
+            let hidden = hidden_name(&name);
+            quote! { #module #hidden }
+        }
+        _ => quote! { #name },
+    }
+}
+
+/// Generate the import of macro when `use` option is given
+///
+/// Import follows the visibility of function,
+/// but public one becomes `pub(crate)` when the macro is not exported.
+fn generate_use(input: &impl Target) -> proc_macro2::TokenStream {
+    let use_token = match &input.options().use_token {
+        Some(use_token) => use_token,
+        None => return quote! {},
+    };
+    let name = input.macro_name();
+    let hidden = hidden_name(&name);
+    let vis = match input.vis() {
+        Visibility::Public(pub_token) if input.options().export.is_none() => {
+            quote_spanned! { pub_token.span => pub(crate) }
+        }
+        vis => vis.to_token_stream(),
+    };
+
+    quote! {
+        #[allow(unused_imports)]
+        #vis #use_token #hidden as #name;
+    }
+}
+
+/// Error for `builder`, `opt` and `defaults` options given to the item other than function
+fn unsupported_options(options: &Options) -> proc_macro2::TokenStream {
+    let builder = options
+        .builder
+        .map(|builder| syn::Error::new(builder.span(), "builder is only supported for functions"));
+    let opt = options
+        .opt
+        .map(|opt| syn::Error::new(opt.span(), "opt is only supported for functions"));
+    let defaults = options.defaults.map(|defaults| {
+        syn::Error::new(defaults.span(), "defaults is only supported for functions")
+    });
+    builder
+        .into_iter()
+        .chain(opt)
+        .chain(defaults)
+        .map(|e| e.to_compile_error())
+        .collect()
+}
+
+/// Give the lifetime to the elided lifetimes in the type
+///
+/// Reference without lifetime like `&str` and `'_` would have the lifetime,
+/// so the type can be used as a field of struct.
+/// `found` is set when any lifetime is given.
+fn name_lifetimes(
+    tokens: proc_macro2::TokenStream,
+    lifetime: &syn::Lifetime,
+    found: &mut bool,
+) -> proc_macro2::TokenStream {
+    let mut result = proc_macro2::TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '&' => {
+                let named =
+                    matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '\'');
+                punct.to_tokens(&mut result);
+                if !named {
+                    *found = true;
+                    lifetime.to_tokens(&mut result);
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => match tokens.peek() {
+                Some(TokenTree::Ident(ident)) if ident == "_" => {
+                    tokens.next();
+                    *found = true;
+                    lifetime.to_tokens(&mut result);
+                }
+                _ => punct.to_tokens(&mut result),
+            },
+            TokenTree::Group(group) => {
+                let mut new = proc_macro2::Group::new(
+                    group.delimiter(),
+                    name_lifetimes(group.stream(), lifetime, found),
+                );
+                new.set_span(group.span());
+                new.to_tokens(&mut result);
+            }
+            token => token.to_tokens(&mut result),
+        }
+    }
+    result
+}
+
+/// Generate the arguments struct with builder when `builder` option is given
+///
+/// For `fn foo(a: u32, b: u32 = 0)`, this would make `FooArgs` with `FooArgs::new(a)` and setter `b(b)`,
+/// and `foo_with(args: FooArgs)` which fills not given arguments with default values in order.
+/// Generics not used by the arguments are kept by the marker, and default generics become the defaults of the struct.
+///
+/// ## Errors
+///
+/// - when argument is not an identifier: `builder needs the name of every argument`
+/// - when argument is `impl Trait`: `builder needs the type of every argument`
+fn generate_builder(input: &DefaultArgs) -> proc_macro2::TokenStream {
+    if input.options.builder.is_none() {
+        return quote! {};
+    }
+
+    let lifetime = syn::Lifetime::new("'__args", proc_macro2::Span::call_site());
+    let mut elided = false;
+    let mut names = Vec::new();
+    let mut types = Vec::new();
+    for fn_arg in &input.args.parsed {
+        match fn_arg {
+            FnArg::Typed(pat) => match arg_name(pat) {
+                Some(name) => {
+                    let ty = &pat.ty;
+                    if mentions(quote! { #ty }, &["impl".to_string()]) {
+                        return syn::Error::new(
+                            ty.span(),
+                            "builder needs the type of every argument",
+                        )
+                        .to_compile_error();
+                    }
+                    names.push(name);
+                    types.push(name_lifetimes(quote! { #ty }, &lifetime, &mut elided));
+                }
+                None => {
+                    return syn::Error::new(pat.span(), "builder needs the name of every argument")
+                        .to_compile_error()
+                }
+            },
+            FnArg::Receiver(_) => unreachable!(),
+        }
+    }
+
+    let required = input.args.required;
+    let (required_names, optional_names) = names.split_at(required);
+    let (required_types, optional_types) = types.split_at(required);
+    let hoisted = input.hoisted();
+    let defaults = (0..input.args.optional.len()).map(|i| input.default_value(i, &hoisted));
+
+    let camel = input
+        .fn_name
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<String>();
+    let args_name = format_ident!("{}Args", camel);
+    let with_name = format_ident!("{}_with", input.fn_name);
+    let fn_ident = input.fn_ident();
+    let doc = format!("Arguments of `{}`", fn_ident);
+    let mut with_doc = format!(
+        "Call `{}` with `{}`, using default values for not given arguments",
+        fn_ident, args_name
+    );
+    if input.unsafety.is_some() {
+        with_doc += &format!("\n\n# Safety\n\nSame as `{}`", fn_ident);
+    }
+    let setter_docs = optional_names
+        .iter()
+        .map(|name| format!("Set `{}` instead of default value", name));
+
+    let vis = &input.vis;
+    let asyncness = &input.asyncness;
+    let unsafety = &input.unsafety;
+    let ret = &input.ret;
+    let mut generics = input.generics.clone();
+    if elided {
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(syn::LifetimeParam::new(lifetime)));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let trailing = input.generic_args.len()
+        - input
+            .generic_args
+            .iter()
+            .rev()
+            .take_while(|arg| arg.default.is_some())
+            .count();
+    let mut index = 0;
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(_) => quote! { #param },
+            _ => {
+                index += 1;
+                match &input.generic_args[index - 1].default {
+                    Some(default) if index > trailing => quote! { #param = #default },
+                    _ => quote! { #param },
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let fields = quote! { #(#types)* };
+    let unused_types = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|ident| !mentions(fields.clone(), &[ident.to_string()]))
+        .collect::<Vec<_>>();
+    let unused_lifetimes = generics
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .filter(|lifetime| !mentions_lifetime(fields.clone(), &[lifetime.ident.to_string()]))
+        .collect::<Vec<_>>();
+    let (marker, marker_value) = if unused_types.is_empty() && unused_lifetimes.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! {
+                __marker: ::core::marker::PhantomData<(#(fn() -> #unused_types,)* #(&#unused_lifetimes (),)*)>,
+            },
+            quote! { __marker: ::core::marker::PhantomData, },
+        )
+    };
+
+    let given = input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(param.ident.to_token_stream()),
+            GenericParam::Const(param) => Some(param.ident.to_token_stream()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let turbofish = GenericArg::turbofish(&input.generic_args, &given);
+
+    let mut call = quote! { #fn_ident #turbofish(#(#names),*) };
+    if asyncness.is_some() {
+        call = quote! { #call.await };
+    }
+    if unsafety.is_some() {
+        call = quote! { unsafe { #call } };
+    }
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #args_name<#(#params),*> #where_clause {
+            #(#required_names: #required_types,)*
+            #(#optional_names: ::core::option::Option<#optional_types>,)*
+            #marker
+        }
+
+        impl #impl_generics #args_name #ty_generics #where_clause {
+            /// Make the arguments with required arguments
+            #[allow(clippy::too_many_arguments)]
+            #vis fn new(#(#required_names: #required_types),*) -> Self {
+                #args_name {
+                    #(#required_names,)*
+                    #(#optional_names: ::core::option::Option::None,)*
+                    #marker_value
+                }
+            }
+
+            #(
+                #[doc = #setter_docs]
+                #[allow(clippy::wrong_self_convention)]
+                #vis fn #optional_names(mut self, #optional_names: #optional_types) -> Self {
+                    self.#optional_names = ::core::option::Option::Some(#optional_names);
+                    self
+                }
+            )*
+        }
+
+        #[doc = #with_doc]
+        #vis #asyncness #unsafety fn #with_name #impl_generics(__args: #args_name #ty_generics) #ret #where_clause {
+            let #args_name { #(#names,)* .. } = __args;
+            #(
+                let #optional_names = match #optional_names {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #defaults,
+                };
+            )*
+            #call
+        }
+    }
+}
+
+/// Generate the function taking `Option` for optional arguments when `opt` option is given
+///
+/// For `fn foo(a: u32, b: u32 = 0)`, this would make `foo_opt(a: u32, b: Option<u32>)`
+/// which uses default value when the argument is `None`.
+fn generate_opt(input: &DefaultArgs) -> proc_macro2::TokenStream {
+    if input.options.opt.is_none() {
+        return quote! {};
+    }
+
+    let required = input.args.required;
+    let hoisted = input.hoisted();
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    let mut bindings = Vec::new();
+    for (i, fn_arg) in input.args.parsed.iter().enumerate() {
+        let pat = match fn_arg {
+            FnArg::Typed(pat) => pat,
+            FnArg::Receiver(_) => unreachable!(),
+        };
+        let binding = binding(pat, i);
+        let ty = &pat.ty;
+        if i < required {
+            params.push(quote! { #binding: #ty });
+        } else {
+            let default = input.default_value(i - required, &hoisted);
+            params.push(quote! { #binding: ::core::option::Option<#ty> });
+            bindings.push(quote! {
+                let #binding = match #binding {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => #default,
+                };
+            });
+        }
+        args.push(binding);
+    }
+
+    let fn_ident = input.fn_ident();
+    let opt_name = format_ident!("{}_opt", input.fn_name);
+    let mut doc = format!(
+        "Call `{}` using default values for `None` arguments",
+        fn_ident
+    );
+    if input.unsafety.is_some() {
+        doc += &format!("\n\n# Safety\n\nSame as `{}`", fn_ident);
+    }
+
+    let vis = &input.vis;
+    let asyncness = &input.asyncness;
+    let unsafety = &input.unsafety;
+    let ret = &input.ret;
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+
+    let mut call = quote! { #fn_ident(#(#args),*) };
+    if asyncness.is_some() {
+        call = quote! { #call.await };
+    }
+    if unsafety.is_some() {
+        call = quote! { unsafe { #call } };
+    }
+
+    quote! {
+        #[doc = #doc]
+        #[allow(clippy::too_many_arguments)]
+        #vis #asyncness #unsafety fn #opt_name #impl_generics(#(#params),*) #ret #where_clause {
+            #(#bindings)*
+            #call
+        }
+    }
+}
+
+/// Generate the items of default values
+///
+/// For `fn foo(a: u32, b: u32 = 0, c: String = String::new())` with `defaults` option,
+/// this would make `FOO_DEFAULT_B` constant and `foo_default_c` function with the visibility of function.
+/// Without the option, they are hidden and may not be used when the arguments are always given.
+/// Function of `unsafe fn` is also unsafe, so the default value can use unsafe operations.
+/// - `input`: target of the items, which can be function, struct or variant
+/// - `unsafety`: whether default values can use unsafe operations
+/// - `item`: name of the target used in the documentation
+fn generate_defaults(
+    input: &impl Target,
+    unsafety: &Option<Token![unsafe]>,
+    item: impl std::fmt::Display,
+) -> proc_macro2::TokenStream {
+    let lifetime = syn::Lifetime::new("'static", proc_macro2::Span::call_site());
+    let vis = input.vis();
+    let args = input.args();
+    let required = args.required;
+    let hidden = if input.options().defaults.is_none() {
+        quote! {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+        }
+    } else {
+        quote! {}
+    };
+
+    input
+        .hoisted()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, hoisted)| {
+            let hoisted = hoisted?;
+            let pat = match &args.parsed[required + i] {
+                FnArg::Typed(pat) => pat,
+                FnArg::Receiver(_) => unreachable!(),
+            };
+            let ty = &pat.ty;
+            let ty = name_lifetimes(quote! { #ty }, &lifetime, &mut false);
+            let default = &args.optional[i].1;
+            let doc = match arg_name(pat) {
+                Some(arg) => format!("Default value of `{}` in `{}`", arg, item),
+                None => format!("Default value of field {} in `{}`", required + i, item),
+            };
+            let safety = if unsafety.is_some() {
+                format!("\n\n# Safety\n\nSame as `{}`", item)
+            } else {
+                String::new()
+            };
+
+            Some(match hoisted {
+                Hoisted::Const(ident) => quote! {
+                    #[doc = #doc]
+                    #hidden
+                    #vis const #ident: #ty = #default;
+                },
+                Hoisted::Fn(ident) if unsafety.is_some() => quote! {
+                    #[doc = #doc]
+                    #[doc = #safety]
+                    #hidden
+                    #[allow(unused_unsafe)]
+                    #vis #unsafety fn #ident() -> #ty {
+                        unsafe { #default }
+                    }
+                },
+                Hoisted::Fn(ident) => quote! {
+                    #[doc = #doc]
+                    #hidden
+                    #vis fn #ident() -> #ty {
+                        #default
+                    }
+                },
+            })
+        })
+        .collect()
+}
+
+/// Generates the macro of the item with its import
+fn expand_macro(input: &impl Target) -> proc_macro2::TokenStream {
+    let name = input.macro_name();
+    let (name, hidden) = match input.options().use_token {
+        Some(_) => (hidden_name(&name), quote! { #[doc(hidden)] }),
+        None => (name, quote! {}),
+    };
+    let export = if input.options().export.is_some() {
+        quote! { #[macro_export] }
+    } else {
+        quote! {}
+    };
+
+    let inner = generate_macro(input);
+    let import = generate_use(input);
+    let reexport = match (input.module(), input.options()) {
+        (
+            Some(_),
+            Options {
+                export: Some(_),
+                use_token: None,
+                ..
+            },
+        ) => {
+            let hidden = hidden_name(&input.macro_name());
+            quote! {
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                pub use #name as #hidden;
+            }
+        }
+        _ => quote! {},
+    };
+
+    quote! {
+        #hidden
+        #export
+        macro_rules! #name {
+            #inner
+        }
+
+        #import
+        #reexport
+    }
+}
+
+/// Generates the macros and the original item
+///
+/// They are returned separately, so macros can be placed before all the items
+/// - `input`: parsed input
+fn expand(input: &DefaultArgsInput) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match input {
+        DefaultArgsInput::Fn(item) => {
+            let check = generate_check(
+                &item.args,
+                &[&item.generics],
+                &item.unsafety,
+                &item.hoisted(),
+            );
+            let mut fn_ident = item.fn_ident();
+            fn_ident.set_span(item.fn_name.span());
+            let path_check = generate_path_check(&item.crate_path, &item.fn_path, &fn_ident);
+            let builder = generate_builder(item);
+            let opt = generate_opt(item);
+            let defaults = generate_defaults(&**item, &item.unsafety, item.fn_ident());
+            (
+                expand_macro(&**item),
+                quote! {
+                    #item
+
+                    #check
+                    #path_check
+                    #builder
+                    #opt
+                    #defaults
+                },
+            )
+        }
+        DefaultArgsInput::Impl(block) => {
+            let macros = block.functions().map(expand_macro);
+            let errors = block
+                .functions()
+                .map(|item| unsupported_options(&item.options));
+            let checks = block.functions().map(|item| {
+                generate_check(
+                    &item.args,
+                    &[&block.generics, &item.generics],
+                    &item.unsafety,
+                    &item.hoisted(),
+                )
+            });
+            (
+                quote! { #(#macros)* },
+                quote! {
+                    #block
+
+                    #(#checks)*
+                    #(#errors)*
+                },
+            )
+        }
+        DefaultArgsInput::Trait(block) => {
+            let macros = block.functions().map(expand_macro);
+            let errors = block
+                .functions()
+                .map(|item| unsupported_options(&item.options));
+            let checks = block.functions().map(|item| {
+                generate_check(
+                    &item.args,
+                    &[&block.generics, &item.generics],
+                    &item.unsafety,
+                    &item.hoisted(),
+                )
+            });
+            let defaults = block.functions().map(|item| {
+                generate_defaults(
+                    item,
+                    &item.unsafety,
+                    format_args!("{}::{}", block.ident, item.fn_ident()),
+                )
+            });
+            let path_check =
+                generate_path_check(&block.crate_path, &block.trait_path, &block.ident);
+            (
+                quote! { #(#macros)* },
+                quote! {
+                    #block
+
+                    #(#checks)*
+                    #path_check
+                    #(#defaults)*
+                    #(#errors)*
+                },
+            )
+        }
+        DefaultArgsInput::Struct(item) => {
+            let check =
+                generate_check(&item.fields.args, &[&item.generics], &None, &item.hoisted());
+            let defaults = generate_defaults(&**item, &None, &item.ident);
+            let error = unsupported_options(&item.options);
+            let path_check = generate_path_check(&item.crate_path, &item.struct_path, &item.ident);
+            (
+                expand_macro(&**item),
+                quote! {
+                    #item
+
+                    #check
+                    #path_check
+                    #defaults
+                    #error
+                },
+            )
+        }
+        DefaultArgsInput::Enum(block) => {
+            let macros = block.variants().map(expand_macro);
+            let errors = block
+                .variants()
+                .map(|variant| unsupported_options(&variant.options));
+            let checks = block.variants().map(|variant| {
+                generate_check(
+                    variant.args(),
+                    &[&block.generics],
+                    &None,
+                    &variant.hoisted(),
+                )
+            });
+            let defaults = block.variants().map(|variant| {
+                generate_defaults(
+                    variant,
+                    &None,
+                    format_args!("{}::{}", block.ident, variant.ident),
+                )
+            });
+            let path_check = generate_path_check(&block.crate_path, &block.enum_path, &block.ident);
+            (
+                quote! { #(#macros)* },
+                quote! {
+                    #block
+
+                    #(#checks)*
+                    #path_check
+                    #(#defaults)*
+                    #(#errors)*
+                },
+            )
+        }
+    }
+}
+
+/// The main macro of this crate
+///
+/// This would generate the original functions and the macros.
+/// All macros come before the functions, so functions in one block can call each other with macros.
 #[proc_macro]
 pub fn default_args(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DefaultArgs);
+    let inputs = parse_macro_input!(input as DefaultArgsInputs);
+    let (macros, items): (Vec<_>, Vec<_>) = inputs.0.iter().map(expand).unzip();
+
+    quote! {
+        #(#macros)*
+        #(#items)*
+    }
+    .into()
+}
+
+/// Attribute form of `default_args!`
+///
+/// Default values are given by `#[default(..)]` attribute of arguments,
+/// and options are given as arguments of the attribute like `#[with_defaults(export)]`
+#[proc_macro_attribute]
+pub fn with_defaults(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(attr as Options);
+    let mut input = parse_macro_input!(input as DefaultArgsInput);
 
-    let name = &input.fn_name;
-    let export = if input.export.is_some() {
-        quote! { #[macro_export] }
-    } else {
-        quote! {}
+    let targets = match &mut input {
+        DefaultArgsInput::Fn(item) => vec![&mut item.options],
+        DefaultArgsInput::Impl(block) => block
+            .items
+            .iter_mut()
+            .filter_map(|item| match item {
+                ImplItem::Fn(item) => Some(&mut item.options),
+                ImplItem::Other(_) => None,
+            })
+            .collect(),
+        DefaultArgsInput::Trait(block) => block
+            .items
+            .iter_mut()
+            .filter_map(|item| match item {
+                TraitItem::Fn(item) => Some(&mut item.options),
+                TraitItem::Other(_) => None,
+            })
+            .collect(),
+        DefaultArgsInput::Struct(item) => vec![&mut item.options],
+        DefaultArgsInput::Enum(block) => block
+            .variants
+            .iter_mut()
+            .map(|variant| &mut variant.options)
+            .collect(),
     };
+    for target in targets {
+        if let Err(e) = target.merge(options.clone()) {
+            return e.to_compile_error().into();
+        }
+    }
 
-    let inner = generate_macro(&input);
+    let (macros, items) = expand(&input);
 
-    let output = quote! {
-        #input
+    quote! {
+        #macros
+        #items
+    }
+    .into()
+}
 
-        #export
-        macro_rules! #name {
-            #inner
+/// Input of `__error!`
+///
+/// This is either `token "message"` or `name [named arguments] [unnamed only arguments]`,
+/// with `[required arguments]` at the end when the argument is given with `?=`
+struct ArgumentError {
+    at: TokenTree,
+    message: Option<LitStr>,
+    named: Vec<Ident>,
+    positional: Vec<Ident>,
+    required: Vec<Ident>,
+}
+
+impl Parse for ArgumentError {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = input.parse()?;
+        if input.peek(LitStr) {
+            return Ok(ArgumentError {
+                at,
+                message: Some(input.parse()?),
+                named: Vec::new(),
+                positional: Vec::new(),
+                required: Vec::new(),
+            });
+        }
+
+        let idents = |input: ParseStream| -> syn::Result<Vec<Ident>> {
+            let content;
+            bracketed!(content in input);
+            let mut idents = Vec::new();
+            while !content.is_empty() {
+                idents.push(content.parse()?);
+            }
+            Ok(idents)
+        };
+        Ok(ArgumentError {
+            at,
+            message: None,
+            named: idents(input)?,
+            positional: idents(input)?,
+            required: if input.is_empty() {
+                Vec::new()
+            } else {
+                idents(input)?
+            },
+        })
+    }
+}
+
+/// Edit distance between two strings
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut dp = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut prev = dp[0];
+        dp[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let cur = dp[j + 1];
+            dp[j + 1] = if a == *b {
+                prev
+            } else {
+                prev.min(dp[j]).min(dp[j + 1]) + 1
+            };
+            prev = cur;
+        }
+    }
+    dp[b.len()]
+}
+
+/// Reports error of the arguments in generated macro
+///
+/// This is used by generated macro, not intended to be used directly
+#[doc(hidden)]
+#[proc_macro]
+pub fn __error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ArgumentError);
+
+    let message = if let Some(message) = input.message {
+        message.value()
+    } else {
+        let name = input.at.to_string();
+        if input.required.iter().any(|i| *i == name) {
+            "`?=` can only be used for optional argument".to_string()
+        } else if input.named.iter().any(|i| *i == name) {
+            format!("argument `{}` given twice", name)
+        } else if input.positional.iter().any(|i| *i == name) {
+            format!("argument `{}` cannot be named", name)
+        } else {
+            let suggestion = input
+                .named
+                .iter()
+                .map(|i| (distance(&name, &i.to_string()), i))
+                .filter(|(d, _)| *d <= name.len().max(3) / 3)
+                .min_by_key(|(d, _)| *d);
+            match suggestion {
+                Some((_, i)) => format!("unknown argument `{}`, did you mean `{}`?", name, i),
+                None => format!("unknown argument `{}`", name),
+            }
         }
     };
-    output.into()
+
+    syn::Error::new(input.at.span(), message)
+        .to_compile_error()
+        .into()
 }
 
 /// This is a test for compile failure
 /// This will check the error cases
 #[allow(dead_code)]
 mod compile_fail_test {
-    /// using `self` in argument is compile error for now
+    /// using `self` outside of impl block is an error
+    ///
+    /// error: `self is only allowed in impl or trait block`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(&self, a: usize, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    /// ```
+    fn self_outside_impl() {}
+
+    /// path cannot be used for functions in impl block
     ///
-    /// error: `self in default_args! is not supported in this version`
+    /// error: `path is not allowed for methods`
     ///
     /// ```compile_fail
     /// # extern crate default_args;
@@ -536,15 +3807,29 @@ mod compile_fail_test {
     ///
     /// struct A {}
     ///
-    /// impl A {
-    ///     default_args! {
-    ///         fn foo(&self, a: usize, b: usize = 0) -> usize {
+    /// default_args! {
+    ///     impl A {
+    ///         fn crate::A::foo(a: usize, b: usize = 0) -> usize {
     ///             a + b
     ///         }
     ///     }
     /// }
     /// ```
-    fn using_self() {}
+    fn path_in_impl() {}
+
+    /// function without body can only be in trait
+    ///
+    /// error: `function without body is only allowed in trait`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize = 0) -> usize;
+    /// }
+    /// ```
+    fn function_without_body() {}
 
     /// having required argument after optional argument is an error
     ///
@@ -562,6 +3847,215 @@ mod compile_fail_test {
     /// ```
     fn required_after_optional() {}
 
+    /// giving default value in both ways is an error
+    ///
+    /// error: `default value is given twice`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, #[default(0)] b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    /// ```
+    fn default_given_twice() {}
+
+    /// name of function can be given in only one way
+    ///
+    /// error: `` only one of `keep_name`, `name` and `suffix` can be used ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     #[default_args(keep_name, suffix = "_impl")]
+    ///     fn foo(a: usize = 0) -> usize {
+    ///         a
+    ///     }
+    /// }
+    /// ```
+    fn name_given_twice() {}
+
+    /// named argument that doesn't exist is an error
+    ///
+    /// error: ``unknown argument `c` ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(1, c = 2);
+    /// ```
+    fn unknown_argument() {}
+
+    /// named argument with typo is an error with suggestion
+    ///
+    /// error: ``unknown argument `optinal`, did you mean `optional`?``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, optional: usize = 0) -> usize {
+    ///         a + optional
+    ///     }
+    /// }
+    ///
+    /// foo!(1, optinal = 2);
+    /// ```
+    fn unknown_argument_with_suggestion() {}
+
+    /// giving same argument twice is an error
+    ///
+    /// error: ``argument `b` given twice``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(1, b = 2, b = 3);
+    /// ```
+    fn argument_given_twice() {}
+
+    /// unnamed argument after named argument is an error
+    ///
+    /// error: `unnamed argument cannot come after named argument`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(a = 1, 2);
+    /// ```
+    fn unnamed_after_named() {}
+
+    /// type of default value is checked even if the macro is not used
+    ///
+    /// error: ``expected `u32`, found `&str` ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: u32 = "a") -> u32 {
+    ///         a
+    ///     }
+    /// }
+    /// ```
+    fn default_type_mismatch() {}
+
+    /// required argument should be given either unnamed or named
+    ///
+    /// error: ``missing required argument `a` ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize, c: usize = 0) -> usize {
+    ///         a + b + c
+    ///     }
+    /// }
+    ///
+    /// foo!(b = 1, c = 2);
+    /// ```
+    fn missing_required() {}
+
+    /// `?=` can only be used for optional argument
+    ///
+    /// error: `` `?=` can only be used for optional argument ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(a ?= Some(1));
+    /// ```
+    fn optional_value_for_required() {}
+
+    /// argument before `/` cannot be named
+    ///
+    /// error: ``argument `a` cannot be named``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, /, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(a = 1);
+    /// ```
+    fn named_positional_only() {}
+
+    /// argument after `*` cannot be unnamed
+    ///
+    /// error: `too many unnamed arguments`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, *, b: usize = 0) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// foo!(1, 2);
+    /// ```
+    fn unnamed_keyword_only() {}
+
+    /// `/` should come before `*`
+    ///
+    /// error: `` `/` should come before `*` ``
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     fn foo(a: usize, *, b: usize = 0, /) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    /// ```
+    fn slash_after_star() {}
+
     /// if path is used in function name, it should start with crate
     ///
     /// error: `path should start with crate`
@@ -577,4 +4071,71 @@ mod compile_fail_test {
     /// }
     /// ```
     fn path_not_starting_with_crate() {}
+
+    /// relative path cannot be used in function name
+    ///
+    /// error: `relative path cannot be resolved where the macro is called, path should start with crate`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// mod foo {
+    ///     use default_args::default_args;
+    ///
+    ///     default_args! {
+    ///         fn self::bar() {}
+    ///     }
+    /// }
+    /// ```
+    fn relative_path() {}
+
+    /// relative path cannot be used in struct name
+    ///
+    /// error: `relative path cannot be resolved where the macro is called, path should start with crate`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// mod foo {
+    ///     use default_args::default_args;
+    ///
+    ///     default_args! {
+    ///         struct super::Bar {
+    ///             a: u32 = 0,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn relative_struct_path() {}
+
+    /// path of function should point to the function
+    ///
+    /// error: `unresolved import`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// mod foo {
+    ///     use default_args::default_args;
+    ///
+    ///     default_args! {
+    ///         fn crate::bar::bar() {}
+    ///     }
+    /// }
+    /// ```
+    fn wrong_path() {}
+
+    /// builder cannot be made for `impl Trait` argument
+    ///
+    /// error: `builder needs the type of every argument`
+    ///
+    /// ```compile_fail
+    /// # extern crate default_args;
+    /// use default_args::default_args;
+    ///
+    /// default_args! {
+    ///     #[default_args(builder)]
+    ///     fn foo(a: impl Into<u32>, b: u32 = 0) -> u32 {
+    ///         a.into() + b
+    ///     }
+    /// }
+    /// ```
+    fn builder_impl_trait() {}
 }
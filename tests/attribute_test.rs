@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod attribute {
+    use default_args::with_defaults;
+
+    #[test]
+    fn attribute_test() {
+        #[with_defaults]
+        fn foo(a: u32, #[default(10)] b: u32, #[default(2)] c: u32) -> u32 {
+            a + b + c
+        }
+
+        assert_eq!(foo!(1), 13);
+        assert_eq!(foo!(1, 2), 5);
+        assert_eq!(foo!(1, c = 3), 14);
+        assert_eq!(foo!(1, c = 3, b = 4), 8);
+    }
+
+    #[test]
+    fn method_test() {
+        struct A(u32);
+
+        #[with_defaults]
+        impl A {
+            fn get(&self, #[default(1)] a: u32) -> u32 {
+                self.0 + a
+            }
+        }
+
+        let a = A(1);
+        assert_eq!(A_get!(a), 2);
+        assert_eq!(A_get!(a, a = 2), 3);
+    }
+}
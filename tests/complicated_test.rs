@@ -32,6 +32,78 @@ mod complicated {
         assert_eq!(foo!(d = 10), 70);
     }
 
+    #[test]
+    fn many_optional() {
+        default_args! {
+            #[allow(clippy::too_many_arguments)]
+            fn foo(
+                a: u32 = 1,
+                b: u32 = 2,
+                c: u32 = 3,
+                d: u32 = 4,
+                e: u32 = 5,
+                f: u32 = 6,
+                g: u32 = 7,
+                h: u32 = 8,
+            ) -> u32 {
+                a * 10000000 + b * 1000000 + c * 100000 + d * 10000 + e * 1000 + f * 100 + g * 10 + h
+            }
+        }
+
+        assert_eq!(foo!(), 12345678);
+        assert_eq!(foo!(0, h = 0, b = 0), 345670);
+        assert_eq!(
+            foo!(g = 0, f = 0, e = 0, d = 0, c = 0, b = 0, a = 0, h = 0),
+            0
+        );
+        assert_eq!(foo!(0, 0, 0, 0, 0, 0, 0, 0,), 0);
+    }
+
+    #[test]
+    fn previous_argument() {
+        default_args! {
+            fn foo(start: u32, len: u32 = 1, end: u32 = start + len) -> (u32, u32, u32) {
+                (start, len, end)
+            }
+        }
+
+        assert_eq!(foo!(1), (1, 1, 2));
+        assert_eq!(foo!(1, 2), (1, 2, 3));
+        assert_eq!(foo!(1, end = 10), (1, 1, 10));
+        assert_eq!(foo!(1, end = 10, len = 3), (1, 3, 10));
+
+        let start = 100;
+        assert_eq!(foo!(start - 99, len = start), (1, 100, 101));
+    }
+
+    #[test]
+    fn named_required() {
+        default_args! {
+            fn transfer(from: u32, to: u32, amount: u64 = 0) -> (u32, u32, u64) {
+                (from, to, amount)
+            }
+        }
+
+        assert_eq!(transfer!(to = 2, from = 1), (1, 2, 0));
+        assert_eq!(transfer!(1, to = 2), (1, 2, 0));
+        assert_eq!(transfer!(amount = 3, to = 2, from = 1), (1, 2, 3));
+        assert_eq!(transfer!(1, 2, 3), (1, 2, 3));
+    }
+
+    #[test]
+    fn markers() {
+        default_args! {
+            fn foo(a: u32, /, b: u32, c: u32 = 3, *, d: u32 = 4) -> (u32, u32, u32, u32) {
+                (a, b, c, d)
+            }
+        }
+
+        assert_eq!(foo!(1, 2), (1, 2, 3, 4));
+        assert_eq!(foo!(1, b = 2), (1, 2, 3, 4));
+        assert_eq!(foo!(1, 2, 0), (1, 2, 0, 4));
+        assert_eq!(foo!(1, d = 0, b = 2), (1, 2, 3, 0));
+    }
+
     #[test]
     fn generics_test() {
         default_args! {
@@ -46,6 +118,202 @@ mod complicated {
         assert_eq!(foo!(a = String::from("abcd")), "abcd");
     }
 
+    #[test]
+    fn default_generics_test() {
+        default_args! {
+            fn foo<T: Default, const N: usize = 2>(a: u32, items: Vec<T> = Vec::new()) -> (u32, Vec<T>, [T; N]) {
+                (a, items, std::array::from_fn(|_| T::default()))
+            }
+
+            fn bar<T = u32>(a: Option<T> = None) -> Option<T> {
+                a
+            }
+        }
+
+        assert_eq!(foo!(1, vec![1u8]), (1, vec![1], [0, 0]));
+        assert_eq!(
+            foo!(<String>; 1),
+            (1, vec![], [String::new(), String::new()])
+        );
+        assert_eq!(foo!(<u8, 3>; 1, items = vec![2]), (1, vec![2], [0, 0, 0]));
+        assert_eq!(bar!(), None);
+        assert_eq!(bar!(Some(1)), Some(1u32));
+        assert_eq!(bar!(<&str>; Some("a")), Some("a"));
+    }
+
+    #[test]
+    fn recursive_test() {
+        default_args! {
+            fn depth(n: u32, acc: u32 = 0) -> u32 {
+                if n == 0 {
+                    acc
+                } else {
+                    depth!(n / 2, acc = acc + 1)
+                }
+            }
+        }
+
+        assert_eq!(depth!(0), 0);
+        assert_eq!(depth!(8), 4);
+    }
+
+    #[test]
+    fn multiple_test() {
+        default_args! {
+            fn first(a: u32 = 1) -> u32 {
+                if a == 0 {
+                    0
+                } else {
+                    second!(a - 1) + 1
+                }
+            }
+
+            fn second(a: u32, b: u32 = 10) -> u32 {
+                first!(a) + b
+            }
+
+            fn plain() -> u32 {
+                first!(2)
+            }
+        }
+
+        assert_eq!(first!(0), 0);
+        assert_eq!(second!(0), 10);
+        assert_eq!(first!(), 11);
+        assert_eq!(plain!(), 22);
+    }
+
+    #[test]
+    fn optional_value_test() {
+        default_args! {
+            fn foo(a: u32, b: u32 = 10, c: u32 = b + 1) -> (u32, u32, u32) {
+                (a, b, c)
+            }
+        }
+
+        let some = Some(2);
+        let none: Option<u32> = None;
+        assert_eq!(foo!(1, b? = some), (1, 2, 3));
+        assert_eq!(foo!(1, b? = none), (1, 10, 11));
+        assert_eq!(foo!(1, c? = none, b = 5), (1, 5, 6));
+        assert_eq!(foo!(a = 0, c? = Some(0)), (0, 10, 0));
+    }
+
+    #[test]
+    fn evaluation_order_test() {
+        use std::cell::RefCell;
+
+        default_args! {
+            fn foo(a: u32, b: u32 = 0, c: u32 = 0, d: u32 = 0) -> (u32, u32, u32, u32) {
+                (a, b, c, d)
+            }
+        }
+
+        let order = RefCell::new(Vec::new());
+        let log = |name: &'static str, value: u32| {
+            order.borrow_mut().push(name);
+            value
+        };
+
+        assert_eq!(
+            foo!(log("a", 1), d = log("d", 4), c = log("c", 3)),
+            (1, 0, 3, 4)
+        );
+        assert_eq!(order.take(), ["a", "d", "c"]);
+
+        assert_eq!(
+            foo!(c = log("c", 3), a = log("a", 1), b? = Some(log("b", 2))),
+            (1, 2, 3, 0)
+        );
+        assert_eq!(order.take(), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn default_order_test() {
+        use std::cell::RefCell;
+
+        default_args! {
+            fn foo(a: u32, b: u32, c: u32 = b, d: u32 = 0) -> (u32, u32, u32, u32) {
+                (a, b, c, d)
+            }
+        }
+
+        let order = RefCell::new(Vec::new());
+        let log = |name: &'static str, value: u32| {
+            order.borrow_mut().push(name);
+            value
+        };
+
+        assert_eq!(foo!(log("a", 1), log("b", 2)), (1, 2, 2, 0));
+        assert_eq!(order.take(), ["a", "b"]);
+
+        assert_eq!(
+            foo!(log("a", 1), log("b", 2), d = log("d", 4)),
+            (1, 2, 2, 4)
+        );
+        assert_eq!(order.take(), ["a", "b", "d"]);
+
+        assert_eq!(
+            foo!(log("a", 1), log("b", 2), log("c", 3), log("d", 4)),
+            (1, 2, 3, 4)
+        );
+        assert_eq!(order.take(), ["a", "b", "c", "d"]);
+
+        assert_eq!(
+            foo!(d = log("d", 4), b = log("b", 2), a = log("a", 1)),
+            (1, 2, 2, 4)
+        );
+        assert_eq!(order.take(), ["d", "b", "a"]);
+
+        assert_eq!(
+            foo!(log("a", 1), c = log("c", 3), b = log("b", 2)),
+            (1, 2, 3, 0)
+        );
+        assert_eq!(order.take(), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn same_name_test() {
+        fn timeout() -> u64 {
+            30
+        }
+
+        default_args! {
+            fn connect(host: &str, timeout: u64 = timeout()) -> String {
+                format!("{}:{}", host, timeout)
+            }
+        }
+
+        assert_eq!(connect!("a"), "a:30");
+        assert_eq!(connect!("a", 1), "a:1");
+    }
+
+    #[test]
+    fn moved_argument_test() {
+        default_args! {
+            fn count(v: Vec<u8>, n: usize = v.len()) -> (Vec<u8>, usize) {
+                (v, n)
+            }
+        }
+
+        assert_eq!(count!(vec![1, 2]), (vec![1, 2], 2));
+        assert_eq!(count!(vec![1], 5), (vec![1], 5));
+        assert_eq!(count!(n = 0, v = vec![3]), (vec![3], 0));
+    }
+
+    #[test]
+    fn closure_test() {
+        default_args! {
+            fn apply<F: Fn(&str) -> usize>(f: F, s: &str = "abc") -> usize {
+                f(s)
+            }
+        }
+
+        assert_eq!(apply!(|s| s.len()), 3);
+        assert_eq!(apply!(|s| s.len(), "ab"), 2);
+        assert_eq!(apply!(f = |s| s.len(), s = "a"), 1);
+    }
+
     #[test]
     fn const_test() {
         default_args! {
@@ -56,8 +324,10 @@ mod complicated {
 
         const A: u32 = foo!();
         const B: u32 = foo!(1);
+        const C: u32 = foo!(a = 2);
         assert_eq!(A, 0);
         assert_eq!(B, 1);
+        assert_eq!(C, 2);
     }
 
     #[test]
@@ -97,4 +367,21 @@ mod complicated {
         assert_eq!(foo!(), 0);
         assert_eq!(foo!(1), 1);
     }
+
+    #[test]
+    fn no_mangle_test() {
+        default_args! {
+            #[no_mangle]
+            pub extern "C" fn default_args_no_mangle_test(a: u32 = 0) -> u32 {
+                a
+            }
+        }
+
+        extern "C" {
+            fn default_args_no_mangle_test(a: u32) -> u32;
+        }
+
+        assert_eq!(default_args_no_mangle_test!(), 0);
+        assert_eq!(unsafe { default_args_no_mangle_test(1) }, 1);
+    }
 }
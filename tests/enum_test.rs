@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod enums {
+    use default_args::default_args;
+
+    default_args! {
+        #[derive(Debug, PartialEq)]
+        pub enum Command {
+            Connect {
+                host: &'static str,
+                port: u16 = 443,
+                secure: bool = port == 443,
+            },
+            Ping(u32 = 0),
+            Send(&'static str, usize = 1),
+            Quit,
+        }
+    }
+
+    #[test]
+    fn struct_variant_test() {
+        assert_eq!(
+            Command_Connect!("localhost"),
+            Command::Connect {
+                host: "localhost",
+                port: 443,
+                secure: true
+            }
+        );
+        assert_eq!(
+            Command_Connect!(port = 80, host = "localhost"),
+            Command::Connect {
+                host: "localhost",
+                port: 80,
+                secure: false
+            }
+        );
+    }
+
+    #[test]
+    fn tuple_variant_test() {
+        assert_eq!(Command_Ping!(), Command::Ping(0));
+        assert_eq!(Command_Ping!(3), Command::Ping(3));
+        assert_eq!(Command_Send!("a"), Command::Send("a", 1));
+        assert_eq!(Command_Send!("a", 2), Command::Send("a", 2));
+        assert_eq!(Command::Quit, Command::Quit);
+    }
+
+    pub mod path {
+        use default_args::default_args;
+
+        default_args! {
+            #[default_args(use)]
+            pub enum crate::enums::path::Shape<T> {
+                Square { side: T },
+                #[default_args(macro_name = circle)]
+                Circle(T, Option<T> = None),
+            }
+        }
+    }
+
+    #[test]
+    fn path_test() {
+        use path::{circle, Shape, Shape_Square};
+
+        assert!(matches!(Shape_Square!(side = 1), Shape::Square { side: 1 }));
+        assert!(matches!(circle!(2), Shape::Circle(2, None)));
+    }
+}
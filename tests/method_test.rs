@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod method {
+    use default_args::default_args;
+
+    struct A {
+        value: u32,
+    }
+
+    default_args! {
+        impl A {
+            fn get(&self, a: u32 = 0) -> u32 {
+                self.value + a
+            }
+        }
+    }
+
+    default_args! {
+        impl A {
+            fn add(&mut self, a: u32 = 1) {
+                self.value += a;
+            }
+        }
+    }
+
+    default_args! {
+        impl A {
+            fn into_value(self, a: u32, b: u32 = 0) -> u32 {
+                self.value + a + b
+            }
+        }
+    }
+
+    default_args! {
+        impl A {
+            #[allow(clippy::boxed_local)]
+            fn boxed(self: Box<Self>, a: u32 = 10) -> u32 {
+                self.value * a
+            }
+        }
+    }
+
+    #[test]
+    fn ref_test() {
+        let a = A { value: 1 };
+        assert_eq!(A_get!(a), 1);
+        assert_eq!(A_get!(a, 2), 3);
+        assert_eq!(A_get!(a, a = 3), 4);
+    }
+
+    #[test]
+    fn mut_test() {
+        let mut a = A { value: 1 };
+        A_add!(a);
+        assert_eq!(a.value, 2);
+        A_add!(a, a = 3);
+        assert_eq!(a.value, 5);
+    }
+
+    #[test]
+    fn value_test() {
+        assert_eq!(A_into_value!(A { value: 1 }, 2), 3);
+        assert_eq!(A_into_value!(A { value: 1 }, 2, b = 3), 6);
+    }
+
+    #[test]
+    fn box_test() {
+        assert_eq!(A_boxed!(Box::new(A { value: 2 })), 20);
+        assert_eq!(A_boxed!(Box::new(A { value: 2 }), 3), 6);
+    }
+}
+
+#[cfg(test)]
+mod associated {
+    use default_args::default_args;
+
+    #[derive(Debug, PartialEq)]
+    pub struct Server {
+        host: &'static str,
+        port: u16,
+    }
+
+    default_args! {
+        impl Server {
+            pub const LOCAL_HOST: &'static str = "127.0.0.1";
+
+            fn new(host: &'static str = "0.0.0.0", port: u16 = 80) -> Self {
+                Server { host, port }
+            }
+
+            fn port(&self, offset: u16 = 0) -> u16 {
+                self.port + offset
+            }
+
+            #[default_args(macro_name = local_server)]
+            fn local(port: u16 = 8080) -> Self {
+                Server_new!("127.0.0.1", port)
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Wrapper<'a, T, const N: usize>(&'a [T; N], usize);
+
+    default_args! {
+        impl<'a, T, const N: usize> Wrapper<'a, T, N> {
+            fn new(items: &'a [T; N], index: usize = 0) -> Self {
+                Wrapper(items, index)
+            }
+        }
+    }
+
+    pub mod path {
+        use default_args::default_args;
+
+        pub struct Counter(pub u32);
+
+        default_args! {
+            impl crate::associated::path::Counter {
+                #[default_args(use)]
+                pub fn start(value: u32 = 0) -> Self {
+                    Counter(value)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn associated_test() {
+        assert_eq!(
+            Server_new!(),
+            Server {
+                host: "0.0.0.0",
+                port: 80
+            }
+        );
+        assert_eq!(Server_new!(port = 8000).port, 8000);
+        assert_eq!(Server_port!(Server_new!(), 1), 81);
+        assert_eq!(local_server!().host, Server::LOCAL_HOST);
+        assert_eq!(local_server!(1).port, 1);
+    }
+
+    #[test]
+    fn generic_test() {
+        let items = [1, 2, 3];
+        assert_eq!(Wrapper_new!(&items), Wrapper(&items, 0));
+        assert_eq!(Wrapper_new!(&items, index = 2), Wrapper(&items, 2));
+    }
+
+    #[test]
+    fn path_test() {
+        use path::Counter_start;
+
+        assert_eq!(Counter_start!().0, 0);
+        assert_eq!(Counter_start!(3).0, 3);
+    }
+}
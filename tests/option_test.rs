@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod option {
+    use default_args::{default_args, with_defaults};
+
+    #[test]
+    fn keep_name_test() {
+        default_args! {
+            #[default_args(keep_name)]
+            fn foo(a: u32 = 1) -> u32 {
+                a
+            }
+        }
+
+        assert_eq!(foo!(), 1);
+        assert_eq!(foo(2), 2);
+    }
+
+    #[test]
+    fn name_test() {
+        default_args! {
+            #[default_args(name = bar)]
+            fn foo(a: u32 = 1) -> u32 {
+                a
+            }
+        }
+
+        assert_eq!(foo!(), 1);
+        assert_eq!(bar(2), 2);
+    }
+
+    #[test]
+    fn suffix_test() {
+        #[with_defaults(suffix = "_impl")]
+        fn foo(#[default(1)] a: u32) -> u32 {
+            a
+        }
+
+        assert_eq!(foo!(), 1);
+        assert_eq!(foo_impl(2), 2);
+    }
+
+    mod import {
+        use default_args::default_args;
+
+        default_args! {
+            #[default_args(keep_name, use)]
+            pub fn foo(a: u32 = 1) -> u32 {
+                a
+            }
+        }
+    }
+
+    #[test]
+    fn keep_name_use_test() {
+        use import::foo;
+
+        assert_eq!(foo!(), 1);
+        assert_eq!(import::foo(2), 2);
+    }
+
+    #[test]
+    fn builder_test() {
+        default_args! {
+            #[default_args(builder)]
+            fn connect(host: &str, port: u16 = 80, path: &str = if port == 80 { "/" } else { "" }) -> String {
+                format!("{}:{}{}", host, port, path)
+            }
+        }
+
+        assert_eq!(connect!("localhost"), "localhost:80/");
+        assert_eq!(connect_with(ConnectArgs::new("localhost")), "localhost:80/");
+
+        let mut args = ConnectArgs::new("localhost");
+        args = args.port(8080);
+        assert_eq!(connect_with(args), "localhost:8080");
+        assert_eq!(connect_with(ConnectArgs::new("a").path("/b")), "a:80/b");
+    }
+
+    #[test]
+    fn args_builder_test() {
+        default_args! {
+            #[default_args(builder)]
+            fn run(cmd: &str, args: Vec<String> = vec![], verbose: bool = false) -> String {
+                format!("{} {:?} {}", cmd, args, verbose)
+            }
+        }
+
+        assert_eq!(run!("ls"), "ls [] false");
+        assert_eq!(run_with(RunArgs::new("ls")), "ls [] false");
+        assert_eq!(
+            run_with(
+                RunArgs::new("ls")
+                    .args(vec!["-a".to_string()])
+                    .verbose(true)
+            ),
+            "ls [\"-a\"] true"
+        );
+    }
+
+    #[test]
+    fn generic_builder_test() {
+        use std::str::FromStr;
+
+        default_args! {
+            #[default_args(builder)]
+            fn parse<T: FromStr = i32>(s: &str, radix: u32 = 10) -> Option<T> {
+                if radix == 10 {
+                    s.parse().ok()
+                } else {
+                    None
+                }
+            }
+        }
+
+        assert_eq!(parse!("1"), Some(1));
+        assert_eq!(parse_with(<ParseArgs>::new("2")), Some(2));
+        assert_eq!(parse_with(ParseArgs::<u8>::new("3")), Some(3u8));
+        assert_eq!(parse_with(<ParseArgs>::new("4").radix(16)), None);
+    }
+
+    #[test]
+    fn opt_test() {
+        default_args! {
+            #[default_args(opt)]
+            fn foo(a: u32, b: u32 = 10, c: u32 = a + b) -> u32 {
+                a * 100 + b * 10 + c
+            }
+        }
+
+        assert_eq!(foo_opt(1, None, None), foo!(1));
+        assert_eq!(foo_opt(1, Some(2), None), 123);
+        assert_eq!(foo_opt(1, None, Some(0)), foo!(1, c = 0));
+    }
+
+    mod defaults {
+        use default_args::default_args;
+
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Info,
+            Debug,
+        }
+
+        fn local_host() -> String {
+            "localhost".to_string()
+        }
+
+        default_args! {
+            #[default_args(defaults, use)]
+            pub fn crate::option::defaults::connect(
+                host: String = local_host(),
+                port: u16 = 80,
+                level: Level = Level::Info,
+                retry: u16 = port / 10,
+            ) -> (String, Level, u16) {
+                (format!("{}:{}", host, port), level, retry)
+            }
+        }
+    }
+
+    #[macro_use]
+    mod local_defaults {
+        use default_args::default_args;
+
+        default_args! {
+            #[default_args(defaults)]
+            pub fn scale(value: u32, factor: u32 = u32::pow(2, 1), offset: u32 = 1) -> u32 {
+                value * factor + offset
+            }
+
+            #[default_args(defaults)]
+            pub fn pick<'a>(s: &'a str, t: &'a str = "x") -> &'a str {
+                if s.is_empty() {
+                    t
+                } else {
+                    s
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn defaults_test() {
+        use defaults::{connect, Level};
+
+        assert_eq!(defaults::connect_default_host(), "localhost");
+        assert_eq!(defaults::CONNECT_DEFAULT_PORT, 80);
+        assert_eq!(defaults::CONNECT_DEFAULT_LEVEL, Level::Info);
+        assert_eq!(connect!(), ("localhost:80".to_string(), Level::Info, 8));
+        assert_eq!(
+            connect!(port = 8080, level = Level::Debug),
+            ("localhost:8080".to_string(), Level::Debug, 808)
+        );
+    }
+
+    #[test]
+    fn local_defaults_test() {
+        use local_defaults::{pick_, scale_};
+
+        assert_eq!(local_defaults::SCALE_DEFAULT_OFFSET, 1);
+        assert_eq!(local_defaults::scale_default_factor(), 2);
+        assert_eq!(scale!(3), 7);
+        assert_eq!(scale!(3, offset = 0), 6);
+        assert_eq!(pick!(""), "x");
+        assert_eq!(pick!("a"), "a");
+    }
+
+    #[test]
+    fn method_test() {
+        struct A(u32);
+
+        default_args! {
+            impl A {
+                #[default_args(keep_name)]
+                fn get(&self, a: u32 = 1) -> u32 {
+                    self.0 + a
+                }
+            }
+        }
+
+        assert_eq!(A_get!(A(1)), 2);
+        assert_eq!(A(1).get(2), 3);
+    }
+}
@@ -18,3 +18,164 @@ mod path {
         assert_eq!(bar!(), 1);
     }
 }
+
+#[cfg(test)]
+mod import {
+    use default_args::default_args;
+
+    pub mod foo {
+        use super::*;
+
+        default_args! {
+            #[default_args(use)]
+            pub fn crate::import::foo::bar(a: usize = 1) -> usize {
+                a
+            }
+        }
+    }
+
+    #[test]
+    fn use_test() {
+        use foo::bar;
+
+        assert_eq!(bar!(), 1);
+        assert_eq!(bar!(a = 2), 2);
+    }
+
+    #[test]
+    fn path_call_test() {
+        assert_eq!(foo::bar!(), 1);
+        assert_eq!(crate::import::foo::bar!(a = 3), 3);
+    }
+}
+
+#[cfg(test)]
+mod scope {
+    #[macro_use]
+    pub mod foo {
+        use default_args::default_args;
+
+        #[derive(Debug, PartialEq)]
+        pub enum Level {
+            Info,
+            Debug,
+        }
+
+        fn offset() -> usize {
+            10
+        }
+
+        /// # Safety
+        ///
+        /// Always safe
+        pub unsafe fn unsafe_offset() -> usize {
+            20
+        }
+
+        default_args! {
+            pub fn crate::scope::foo::bar(a: usize = offset(), level: Level = Level::Info) -> (usize, Level) {
+                (a, level)
+            }
+
+            pub fn crate::scope::foo::longer<'a>(s: &'a str, t: &'a str = "abc") -> &'a str {
+                if s.len() < t.len() {
+                    t
+                } else {
+                    s
+                }
+            }
+
+            /// # Safety
+            ///
+            /// Always safe
+            pub unsafe fn crate::scope::foo::add(a: usize = unsafe_offset()) -> usize {
+                a + 1
+            }
+
+            #[derive(Debug, PartialEq)]
+            pub struct crate::scope::foo::Point {
+                pub x: usize = offset(),
+                pub y: usize = x + 1,
+            }
+
+            #[derive(Debug, PartialEq)]
+            pub struct crate::scope::foo::Pair(pub usize = offset(), pub Level = Level::Debug);
+
+            #[derive(Debug, PartialEq)]
+            pub enum crate::scope::foo::Shape {
+                Circle { radius: usize = offset() },
+            }
+
+            impl crate::scope::foo::Point {
+                pub fn shift(&self, by: usize = offset()) -> usize {
+                    self.x + by
+                }
+            }
+
+            pub trait crate::scope::foo::Scale {
+                fn scale(&self, by: usize = offset()) -> usize;
+            }
+        }
+
+        impl Scale for Point {
+            fn scale_(&self, by: usize) -> usize {
+                self.x * by
+            }
+        }
+    }
+
+    #[test]
+    fn scope_test() {
+        assert_eq!(bar!(), (10, foo::Level::Info));
+        assert_eq!(bar!(1, foo::Level::Debug), (1, foo::Level::Debug));
+    }
+
+    #[test]
+    fn lifetime_test() {
+        assert_eq!(longer!("a"), "abc");
+        assert_eq!(longer!("abcd", t = "ab"), "abcd");
+    }
+
+    #[test]
+    fn unsafe_test() {
+        assert_eq!(unsafe { add!() }, 21);
+        assert_eq!(unsafe { add!(1) }, 2);
+    }
+
+    #[test]
+    fn struct_test() {
+        assert_eq!(Point!(), foo::Point { x: 10, y: 11 });
+        assert_eq!(Point!(y = 1), foo::Point { x: 10, y: 1 });
+        assert_eq!(Pair!(), foo::Pair(10, foo::Level::Debug));
+        assert_eq!(Shape_Circle!(), foo::Shape::Circle { radius: 10 });
+    }
+
+    #[test]
+    fn method_test() {
+        let point = Point!(1);
+        assert_eq!(Point_shift!(&point), 11);
+        assert_eq!(Point_shift!(&point, 2), 3);
+        assert_eq!(scale!(&point), 10);
+        assert_eq!(scale!(&point, 3), 3);
+    }
+}
+
+#[cfg(test)]
+mod export {
+    #[macro_use]
+    pub mod foo {
+        use default_args::default_args;
+
+        default_args! {
+            export pub fn crate::export::foo::bar(a: usize, b: usize = 1) -> usize {
+                a + b
+            }
+        }
+    }
+
+    #[test]
+    fn export_test() {
+        assert_eq!(bar!(1), 2);
+        assert_eq!(bar!(1, b = 2), 3);
+    }
+}
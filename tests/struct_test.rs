@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod structs {
+    use default_args::{default_args, with_defaults};
+
+    default_args! {
+        #[derive(Debug, PartialEq)]
+        pub struct Config {
+            pub name: String,
+            pub host: &'static str = "localhost",
+            pub port: u16 = 80,
+            pub address: String = format!("{}:{}", host, port),
+        }
+    }
+
+    default_args! {
+        #[derive(Debug, PartialEq)]
+        struct Point(i32 = 0, i32 = 0);
+    }
+
+    #[with_defaults]
+    #[derive(Debug, PartialEq)]
+    struct Wrapper<T> {
+        #[default(Vec::new())]
+        items: Vec<T>,
+    }
+
+    #[test]
+    fn struct_test() {
+        let config = Config!("server".to_string(), port = 8080);
+        assert_eq!(config.name, "server");
+        assert_eq!(config.host, "localhost");
+        assert_eq!(config.address, "localhost:8080");
+        assert_eq!(
+            Config!(name = "a".to_string(), host = "0.0.0.0"),
+            Config {
+                name: "a".to_string(),
+                host: "0.0.0.0",
+                port: 80,
+                address: "0.0.0.0:80".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn tuple_test() {
+        assert_eq!(Point!(), Point(0, 0));
+        assert_eq!(Point!(1), Point(1, 0));
+        assert_eq!(Point::default(), Point(0, 0));
+    }
+
+    #[test]
+    fn default_test() {
+        let wrapper: Wrapper<u32> = Default::default();
+        assert_eq!(wrapper, Wrapper { items: vec![] });
+        assert_eq!(Wrapper!(vec![1]).items, [1]);
+    }
+
+    pub mod path {
+        use default_args::default_args;
+
+        default_args! {
+            #[default_args(use)]
+            pub struct crate::structs::path::Size {
+                pub width: u32 = 1,
+                pub height: u32 = width,
+            }
+        }
+    }
+
+    #[test]
+    fn path_test() {
+        use path::Size;
+
+        let size = Size!(height = 3);
+        assert_eq!((size.width, size.height), (1, 3));
+        assert_eq!(Size::default().height, 1);
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod traits {
+    use default_args::default_args;
+
+    default_args! {
+        pub trait Render {
+            type Output;
+
+            fn render(&self, scale: f32 = 1.0) -> Self::Output;
+
+            fn label(&self, prefix: &str = "widget", suffix: &str = "") -> String {
+                format!("{}{}", prefix, suffix)
+            }
+        }
+    }
+
+    struct Widget(f32);
+
+    impl Render for Widget {
+        type Output = f32;
+
+        fn render_(&self, scale: f32) -> f32 {
+            self.0 * scale
+        }
+    }
+
+    #[test]
+    fn trait_test() {
+        let widget = Widget(2.0);
+        assert_eq!(render!(&widget), 2.0);
+        assert_eq!(render!(&widget, scale = 2.0), 4.0);
+        assert_eq!(label!(&widget), "widget");
+        assert_eq!(label!(&widget, suffix = "!"), "widget!");
+    }
+
+    struct Shadow;
+
+    impl Shadow {
+        #[allow(dead_code)]
+        fn label_(&self, _prefix: &str, _suffix: &str) -> String {
+            "inherent".to_string()
+        }
+    }
+
+    impl Render for Shadow {
+        type Output = ();
+
+        fn render_(&self, _scale: f32) {}
+    }
+
+    #[test]
+    fn inherent_test() {
+        assert_eq!(label!(&Shadow), "widget");
+    }
+
+    pub mod path {
+        use default_args::default_args;
+
+        default_args! {
+            pub trait crate::traits::path::Size {
+                #[default_args(keep_name, use)]
+                fn size(&self, unit: u32 = 1) -> u32;
+            }
+        }
+    }
+
+    impl path::Size for Widget {
+        fn size(&self, unit: u32) -> u32 {
+            self.0 as u32 * unit
+        }
+    }
+
+    #[test]
+    fn path_test() {
+        use path::size;
+
+        assert_eq!(size!(&Widget(3.0)), 3);
+        assert_eq!(size!(&Widget(3.0), 2), 6);
+    }
+}